@@ -13,6 +13,7 @@ pub enum ErrorKind {
     SystemTime { source: std::time::SystemTimeError },
     Configuration { message: String },
     Uuid { message: String },
+    PasswordHashing { message: String },
 }
 
 impl std::fmt::Display for ErrorKind {
@@ -57,6 +58,9 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::Uuid { message } => {
                 write!(formatter, "Uuid error > {}", message)
             }
+            ErrorKind::PasswordHashing { message } => {
+                write!(formatter, "Password hashing > {}", message)
+            }
         }
     }
 }
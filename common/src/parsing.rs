@@ -1,7 +1,8 @@
 use std::io::{Read, Write};
-use std::net::{TcpStream};
 
-use crate::{Result};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{Error, ErrorKind, Result};
 use crate::helpers::{misconfiguration};
 
 use bson::{Document};
@@ -57,24 +58,223 @@ where
     from_reader(bytes)
 }
 
-pub fn read_packet(stream: &mut TcpStream) -> Result<Vec<u8>> {
-    let mut data = vec![0u8; 4];
+/// The frames are BSON documents, which
+/// start with their own length.
+pub const HEADER_SIZE: usize = 4;
 
-    stream.read_exact(&mut data)?;
+/// The smallest possible BSON document:
+/// the length and the trailing zero.
+pub const MIN_FRAME_SIZE: usize = HEADER_SIZE + 1;
 
-    let length = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1024 * 1024;
 
-    data.resize(length, 0);
-    stream.read_exact(&mut data[4..])?;
+/// Reads and writes the length-prefixed
+/// BSON messages, sync or async.
+#[derive(Clone, Copy, Debug)]
+pub struct Codec {
+    pub max_frame_size: usize,
+}
 
-    Ok(data)
+impl Default for Codec {
+    fn default() -> Self {
+        Codec {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
 }
 
-pub fn read_message<M>(stream: &mut TcpStream) -> Result<M>
+impl Codec {
+    pub fn new(max_frame_size: usize) -> Self {
+        Codec {
+            max_frame_size,
+        }
+    }
+
+    /// Checks the length prefix and prepares
+    /// the buffer for the rest of the frame.
+    fn start_frame(&self, header: [u8; HEADER_SIZE]) -> Result<Vec<u8>> {
+        let length = u32::from_le_bytes(header) as usize;
+
+        if length < MIN_FRAME_SIZE {
+            return ErrorKind::MalformedMessage {
+                message: format!("Frame length {} is too small", length)
+            }.into()
+        }
+
+        if length > self.max_frame_size {
+            return ErrorKind::MessageSizeExceeded.into()
+        }
+
+        let mut data = vec![0u8; length];
+        data[..HEADER_SIZE].copy_from_slice(&header);
+        Ok(data)
+    }
+
+    pub fn read_packet<R: Read>(&self, reader: &mut R) -> Result<Vec<u8>> {
+        let mut header = [0u8; HEADER_SIZE];
+
+        if let Err(error) = reader.read_exact(&mut header) {
+            return Err(header_error(error))
+        }
+
+        let mut data = self.start_frame(header)?;
+        reader.read_exact(&mut data[HEADER_SIZE..])?;
+
+        Ok(data)
+    }
+
+    pub async fn read_packet_async<R>(&self, reader: &mut R) -> Result<Vec<u8>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut header = [0u8; HEADER_SIZE];
+
+        if let Err(error) = reader.read_exact(&mut header).await {
+            return Err(header_error(error))
+        }
+
+        let mut data = self.start_frame(header)?;
+        reader.read_exact(&mut data[HEADER_SIZE..]).await?;
+
+        Ok(data)
+    }
+
+    pub fn read_message<M, R>(&self, reader: &mut R) -> Result<M>
+    where
+        R: Read,
+        M: for<'de> serde::Deserialize<'de>,
+    {
+        let packet = self.read_packet(reader)?;
+        from_bytes(&packet)
+    }
+
+    pub async fn read_message_async<M, R>(&self, reader: &mut R) -> Result<M>
+    where
+        R: AsyncRead + Unpin,
+        M: for<'de> serde::Deserialize<'de>,
+    {
+        let packet = self.read_packet_async(reader).await?;
+        from_bytes(&packet)
+    }
+
+    pub fn write_message<W, M>(&self, message: &M, writer: &mut W) -> Result<()>
+    where
+        W: Write,
+        M: serde::Serialize,
+    {
+        let packet = self.finish_frame(message)?;
+        writer.write_all(&packet)?;
+        Ok(())
+    }
+
+    pub async fn write_message_async<W, M>(&self, message: &M, writer: &mut W) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        M: serde::Serialize,
+    {
+        let packet = self.finish_frame(message)?;
+        writer.write_all(&packet).await?;
+        Ok(())
+    }
+
+    /// Serializes the message, making sure the
+    /// other side won't refuse to read it.
+    fn finish_frame<M: serde::Serialize>(&self, message: &M) -> Result<Vec<u8>> {
+        let packet = to_bytes(message)?;
+
+        if packet.len() > self.max_frame_size {
+            return ErrorKind::MessageSizeExceeded.into()
+        }
+
+        Ok(packet)
+    }
+}
+
+/// Running out of data before a frame
+/// has started means the peer is gone.
+fn header_error(error: std::io::Error) -> Error {
+    match error.kind() {
+        std::io::ErrorKind::UnexpectedEof => ErrorKind::NothingToRead.into(),
+        _ => error.into(),
+    }
+}
+
+pub fn read_packet<R: Read>(stream: &mut R) -> Result<Vec<u8>> {
+    Codec::default().read_packet(stream)
+}
+
+pub fn read_message<M, R>(stream: &mut R) -> Result<M>
 where
-    M: for<'de> serde::Deserialize<'de>
+    R: Read,
+    M: for<'de> serde::Deserialize<'de>,
 {
-    let packet = read_packet(stream)?;
-    from_bytes(&packet)
+    Codec::default().read_message(stream)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(length: u32, body: &[u8]) -> Vec<u8> {
+        let mut data = length.to_le_bytes().to_vec();
+        data.extend_from_slice(body);
+        data
+    }
+
+    #[tokio::test]
+    async fn reads_back_what_it_writes() {
+        let codec = Codec::default();
+        let message = doc! { "command": ["cd", "common"] };
+        let mut packet = vec![];
+
+        codec.write_message_async(&message, &mut packet).await.unwrap();
+        let read: Document = codec.read_message_async(&mut packet.as_slice()).await.unwrap();
+
+        assert_eq!(read, message);
+    }
+
+    #[tokio::test]
+    async fn refuses_lengths_shorter_than_a_document() {
+        let data = frame(4, &[0]);
+        let result = Codec::default().read_packet_async(&mut data.as_slice()).await;
+
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::MalformedMessage { .. }));
+    }
+
+    #[tokio::test]
+    async fn refuses_oversize_frames_before_reading_them() {
+        let data = frame(17, &[]);
+        let result = Codec::new(16).read_packet_async(&mut data.as_slice()).await;
+
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::MessageSizeExceeded));
+    }
+
+    #[tokio::test]
+    async fn fails_on_a_truncated_body() {
+        let data = frame(12, &[1, 2, 3]);
+        let result = Codec::default().read_packet_async(&mut data.as_slice()).await;
+
+        match result.unwrap_err().kind {
+            ErrorKind::Io { source } => assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof),
+            other => panic!("Unexpected error > {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn treats_no_data_as_a_disconnection() {
+        let result = Codec::default().read_packet_async(&mut [].as_ref()).await;
+
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::NothingToRead));
+    }
+
+    #[test]
+    fn checks_the_frames_the_same_way_when_blocking() {
+        let codec = Codec::new(16);
+
+        let short = frame(4, &[0]);
+        let oversize = frame(17, &[]);
+
+        assert!(matches!(codec.read_packet(&mut short.as_slice()).unwrap_err().kind, ErrorKind::MalformedMessage { .. }));
+        assert!(matches!(codec.read_packet(&mut oversize.as_slice()).unwrap_err().kind, ErrorKind::MessageSizeExceeded));
+    }
+}
@@ -5,6 +5,8 @@
 use futures::{future, Stream, stream};
 #[allow(unused_imports)]
 use openapi_client::{Api, ApiNoContext, Client, ContextWrapperExt, models,
+                      GetEventsResponse,
+                      GetMeetingResponse,
                       GetMyselfResponse,
                       GetNewUserResponse,
                       PostQueryResponse,
@@ -29,6 +31,8 @@ fn main() {
         .arg(Arg::with_name("operation")
             .help("Sets the operation to run")
             .possible_values(&[
+                "GetEvents",
+                "GetMeeting",
                 "GetMyself",
                 "GetNewUser",
             ])
@@ -74,6 +78,16 @@ fn main() {
     let mut rt = tokio::runtime::Runtime::new().unwrap();
 
     match matches.value_of("operation") {
+        Some("GetEvents") => {
+            let result = rt.block_on(client.get_events(
+            ));
+            info!("{:?} (X-Span-ID: {:?})", result, (client.context() as &dyn Has<XSpanIdString>).get().clone());
+        },
+        Some("GetMeeting") => {
+            let result = rt.block_on(client.get_meeting(
+            ));
+            info!("{:?} (X-Span-ID: {:?})", result, (client.context() as &dyn Has<XSpanIdString>).get().clone());
+        },
         Some("GetMyself") => {
             let result = rt.block_on(client.get_myself(
             ));
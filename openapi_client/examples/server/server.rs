@@ -96,6 +96,8 @@ impl<C> Server<C> {
 
 use openapi_client::{
     Api,
+    GetEventsResponse,
+    GetMeetingResponse,
     GetMyselfResponse,
     GetNewUserResponse,
     PostQueryResponse,
@@ -107,6 +109,26 @@ use swagger::ApiError;
 #[async_trait]
 impl<C> Api<C> for Server<C> where C: Has<XSpanIdString> + Send + Sync
 {
+    /// Wait for something to happen
+    async fn get_events(
+        &self,
+        context: &C) -> Result<GetEventsResponse, ApiError>
+    {
+        let context = context.clone();
+        info!("get_events() - X-Span-ID: {:?}", context.get().0.clone());
+        Err("Generic failure".into())
+    }
+
+    /// Returns the meeting going on
+    async fn get_meeting(
+        &self,
+        context: &C) -> Result<GetMeetingResponse, ApiError>
+    {
+        let context = context.clone();
+        info!("get_meeting() - X-Span-ID: {:?}", context.get().0.clone());
+        Err("Generic failure".into())
+    }
+
     /// Returns your status
     async fn get_myself(
         &self,
@@ -12,6 +12,55 @@ type ServiceError = Box<dyn Error + Send + Sync + 'static>;
 pub const BASE_PATH: &'static str = "";
 pub const API_VERSION: &'static str = "1.0.0";
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+pub enum GetEventsResponse {
+    /// Here is what happened
+    HereIsWhatHappened
+    (models::EventsList)
+    ,
+    /// Your request is junk
+    YourRequestIsJunk
+    (models::Problem)
+    ,
+    /// Who are you
+    WhoAreYou
+    (models::Problem)
+    ,
+    /// Your session has expired
+    YourSessionHasExpired
+    ,
+    /// Something went wrong
+    SomethingWentWrong
+    (models::Problem)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+pub enum GetMeetingResponse {
+    /// Here is the meeting
+    HereIsTheMeeting
+    (models::Meeting)
+    ,
+    /// There is no meeting
+    ThereIsNoMeeting
+    ,
+    /// Your request is junk
+    YourRequestIsJunk
+    (models::Problem)
+    ,
+    /// Who are you
+    WhoAreYou
+    (models::Problem)
+    ,
+    /// Your session has expired
+    YourSessionHasExpired
+    ,
+    /// Something went wrong
+    SomethingWentWrong
+    (models::Problem)
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[must_use]
 pub enum GetMyselfResponse {
@@ -21,6 +70,15 @@ pub enum GetMyselfResponse {
     ,
     /// Your request is junk
     YourRequestIsJunk
+    (models::Problem)
+    ,
+    /// Who are you
+    WhoAreYou
+    (models::Problem)
+    ,
+    /// Something went wrong
+    SomethingWentWrong
+    (models::Problem)
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -32,6 +90,11 @@ pub enum GetNewUserResponse {
     ,
     /// Your request is junk
     YourRequestIsJunk
+    (models::Problem)
+    ,
+    /// Something went wrong
+    SomethingWentWrong
+    (models::Problem)
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -63,9 +126,22 @@ pub enum PostQueryResponse {
     ,
     /// Your request is junk
     YourRequestIsJunk
+    (models::Problem)
+    ,
+    /// Who are you
+    WhoAreYou
+    (models::Problem)
+    ,
+    /// You may not do that
+    YouMayNotDoThat
+    (models::Problem)
     ,
-    /// You are dead
-    YouAreDead
+    /// Your session has expired
+    YourSessionHasExpired
+    ,
+    /// Something went wrong
+    SomethingWentWrong
+    (models::Problem)
 }
 
 /// API
@@ -75,6 +151,16 @@ pub trait Api<C: Send + Sync> {
         Poll::Ready(Ok(()))
     }
 
+    /// Wait for something to happen
+    async fn get_events(
+        &self,
+        context: &C) -> Result<GetEventsResponse, ApiError>;
+
+    /// Returns the meeting going on
+    async fn get_meeting(
+        &self,
+        context: &C) -> Result<GetMeetingResponse, ApiError>;
+
     /// Returns your status
     async fn get_myself(
         &self,
@@ -101,6 +187,16 @@ pub trait ApiNoContext<C: Send + Sync> {
 
     fn context(&self) -> &C;
 
+    /// Wait for something to happen
+    async fn get_events(
+        &self,
+        ) -> Result<GetEventsResponse, ApiError>;
+
+    /// Returns the meeting going on
+    async fn get_meeting(
+        &self,
+        ) -> Result<GetMeetingResponse, ApiError>;
+
     /// Returns your status
     async fn get_myself(
         &self,
@@ -142,6 +238,24 @@ impl<T: Api<C> + Send + Sync, C: Clone + Send + Sync> ApiNoContext<C> for Contex
         ContextWrapper::context(self)
     }
 
+    /// Wait for something to happen
+    async fn get_events(
+        &self,
+        ) -> Result<GetEventsResponse, ApiError>
+    {
+        let context = self.context().clone();
+        self.api().get_events(&context).await
+    }
+
+    /// Returns the meeting going on
+    async fn get_meeting(
+        &self,
+        ) -> Result<GetMeetingResponse, ApiError>
+    {
+        let context = self.context().clone();
+        self.api().get_meeting(&context).await
+    }
+
     /// Returns your status
     async fn get_myself(
         &self,
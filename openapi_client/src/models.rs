@@ -4,18 +4,257 @@ use crate::models;
 #[cfg(any(feature = "client", feature = "server"))]
 use crate::header;
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct Event {
+    #[serde(rename = "kind")]
+    pub kind: String,
+
+    #[serde(rename = "message")]
+    pub message: String,
+
+}
+
+impl Event {
+    pub fn new(kind: String, message: String, ) -> Event {
+        Event {
+            kind: kind,
+            message: message,
+        }
+    }
+}
+
+/// Converts the Event value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for Event {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        params.push("kind".to_string());
+        params.push(self.kind.to_string());
+
+
+        params.push("message".to_string());
+        params.push(self.message.to_string());
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a Event value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub kind: Vec<String>,
+            pub message: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing Event".to_string())
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "kind" => intermediate_rep.kind.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "message" => intermediate_rep.message.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing Event".to_string())
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(Event {
+            kind: intermediate_rep.kind.into_iter().next().ok_or("kind missing in Event".to_string())?,
+            message: intermediate_rep.message.into_iter().next().ok_or("message missing in Event".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Event> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<Event>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Event>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+             std::result::Result::Ok(value) => std::result::Result::Ok(value),
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Invalid header value for Event - value: {} is invalid {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<Event> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+             std::result::Result::Ok(value) => {
+                    match <Event as std::str::FromStr>::from_str(value) {
+                        std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                        std::result::Result::Err(err) => std::result::Result::Err(
+                            format!("Unable to convert header value '{}' into Event - {}",
+                                value, err))
+                    }
+             },
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Unable to convert header: {:?} to string: {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct EventsList {
+    #[serde(rename = "events")]
+    pub events: Vec<models::Event>,
+
+}
+
+impl EventsList {
+    pub fn new(events: Vec<models::Event>, ) -> EventsList {
+        EventsList {
+            events: events,
+        }
+    }
+}
+
+/// Converts the EventsList value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for EventsList {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+        // Skipping events in query parameter serialization
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a EventsList value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for EventsList {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub events: Vec<Vec<models::Event>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing EventsList".to_string())
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "events" => return std::result::Result::Err("Parsing a container in this style is not supported in EventsList".to_string()),
+                    _ => return std::result::Result::Err("Unexpected key while parsing EventsList".to_string())
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(EventsList {
+            events: intermediate_rep.events.into_iter().next().ok_or("events missing in EventsList".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<EventsList> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<EventsList>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<EventsList>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+             std::result::Result::Ok(value) => std::result::Result::Ok(value),
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Invalid header value for EventsList - value: {} is invalid {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<EventsList> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+             std::result::Result::Ok(value) => {
+                    match <EventsList as std::str::FromStr>::from_str(value) {
+                        std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                        std::result::Result::Err(err) => std::result::Result::Err(
+                            format!("Unable to convert header value '{}' into EventsList - {}",
+                                value, err))
+                    }
+             },
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Unable to convert header: {:?} to string: {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct FilesList {
     #[serde(rename = "files")]
     pub files: Vec<String>,
 
+    /// Whose bodies lie here, only seen by the living
+    #[serde(rename = "bodies")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub bodies: Option<Vec<String>>,
+
 }
 
 impl FilesList {
     pub fn new(files: Vec<String>, ) -> FilesList {
         FilesList {
             files: files,
+            bodies: None,
         }
     }
 }
@@ -30,6 +269,12 @@ impl std::string::ToString for FilesList {
         params.push("files".to_string());
         params.push(self.files.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",").to_string());
 
+
+        if let Some(ref bodies) = self.bodies {
+            params.push("bodies".to_string());
+            params.push(bodies.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",").to_string());
+        }
+
         params.join(",").to_string()
     }
 }
@@ -45,6 +290,7 @@ impl std::str::FromStr for FilesList {
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
             pub files: Vec<Vec<String>>,
+            pub bodies: Vec<Vec<String>>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -62,6 +308,7 @@ impl std::str::FromStr for FilesList {
             if let Some(key) = key_result {
                 match key {
                     "files" => return std::result::Result::Err("Parsing a container in this style is not supported in FilesList".to_string()),
+                    "bodies" => return std::result::Result::Err("Parsing a container in this style is not supported in FilesList".to_string()),
                     _ => return std::result::Result::Err("Unexpected key while parsing FilesList".to_string())
                 }
             }
@@ -73,6 +320,7 @@ impl std::str::FromStr for FilesList {
         // Use the intermediate representation to return the struct
         std::result::Result::Ok(FilesList {
             files: intermediate_rep.files.into_iter().next().ok_or("files missing in FilesList".to_string())?,
+            bodies: intermediate_rep.bodies.into_iter().next(),
         })
     }
 }
@@ -252,12 +500,34 @@ pub struct InlineResponse2001 {
     #[serde(rename = "is_alive")]
     pub is_alive: bool,
 
+    #[serde(rename = "remaining_lifetime")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub remaining_lifetime: Option<u64>,
+
+    /// Set once you are a ghost
+    #[serde(rename = "killed_by")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub killed_by: Option<String>,
+
+    /// Set once you are a ghost
+    #[serde(rename = "killed_at")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub killed_at: Option<chrono::DateTime::<chrono::Utc>>,
+
+    #[serde(rename = "meeting")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub meeting: Option<models::Meeting>,
+
 }
 
 impl InlineResponse2001 {
     pub fn new(is_alive: bool, ) -> InlineResponse2001 {
         InlineResponse2001 {
             is_alive: is_alive,
+            remaining_lifetime: None,
+            killed_by: None,
+            killed_at: None,
+            meeting: None,
         }
     }
 }
@@ -272,6 +542,22 @@ impl std::string::ToString for InlineResponse2001 {
         params.push("is_alive".to_string());
         params.push(self.is_alive.to_string());
 
+
+        if let Some(ref remaining_lifetime) = self.remaining_lifetime {
+            params.push("remaining_lifetime".to_string());
+            params.push(remaining_lifetime.to_string());
+        }
+
+
+        if let Some(ref killed_by) = self.killed_by {
+            params.push("killed_by".to_string());
+            params.push(killed_by.to_string());
+        }
+
+        // Skipping killed_at in query parameter serialization
+
+        // Skipping meeting in query parameter serialization
+
         params.join(",").to_string()
     }
 }
@@ -287,6 +573,10 @@ impl std::str::FromStr for InlineResponse2001 {
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
             pub is_alive: Vec<bool>,
+            pub remaining_lifetime: Vec<u64>,
+            pub killed_by: Vec<String>,
+            pub killed_at: Vec<chrono::DateTime::<chrono::Utc>>,
+            pub meeting: Vec<models::Meeting>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -304,6 +594,10 @@ impl std::str::FromStr for InlineResponse2001 {
             if let Some(key) = key_result {
                 match key {
                     "is_alive" => intermediate_rep.is_alive.push(<bool as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "remaining_lifetime" => intermediate_rep.remaining_lifetime.push(<u64 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "killed_by" => intermediate_rep.killed_by.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "killed_at" => intermediate_rep.killed_at.push(<chrono::DateTime::<chrono::Utc> as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "meeting" => intermediate_rep.meeting.push(<models::Meeting as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
                     _ => return std::result::Result::Err("Unexpected key while parsing InlineResponse2001".to_string())
                 }
             }
@@ -315,6 +609,10 @@ impl std::str::FromStr for InlineResponse2001 {
         // Use the intermediate representation to return the struct
         std::result::Result::Ok(InlineResponse2001 {
             is_alive: intermediate_rep.is_alive.into_iter().next().ok_or("is_alive missing in InlineResponse2001".to_string())?,
+            remaining_lifetime: intermediate_rep.remaining_lifetime.into_iter().next(),
+            killed_by: intermediate_rep.killed_by.into_iter().next(),
+            killed_at: intermediate_rep.killed_at.into_iter().next(),
+            meeting: intermediate_rep.meeting.into_iter().next(),
         })
     }
 }
@@ -364,12 +662,27 @@ pub struct KillResult {
     #[serde(rename = "killed_users_count")]
     pub killed_users_count: u32,
 
+    #[serde(rename = "victim")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub victim: Option<String>,
+
+    #[serde(rename = "victim_tag")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub victim_tag: Option<String>,
+
+    /// Seconds until you can kill again
+    #[serde(rename = "cooldown")]
+    pub cooldown: u32,
+
 }
 
 impl KillResult {
-    pub fn new(killed_users_count: u32, ) -> KillResult {
+    pub fn new(killed_users_count: u32, cooldown: u32, ) -> KillResult {
         KillResult {
             killed_users_count: killed_users_count,
+            victim: None,
+            victim_tag: None,
+            cooldown: cooldown,
         }
     }
 }
@@ -384,6 +697,22 @@ impl std::string::ToString for KillResult {
         params.push("killed_users_count".to_string());
         params.push(self.killed_users_count.to_string());
 
+
+        if let Some(ref victim) = self.victim {
+            params.push("victim".to_string());
+            params.push(victim.to_string());
+        }
+
+
+        if let Some(ref victim_tag) = self.victim_tag {
+            params.push("victim_tag".to_string());
+            params.push(victim_tag.to_string());
+        }
+
+
+        params.push("cooldown".to_string());
+        params.push(self.cooldown.to_string());
+
         params.join(",").to_string()
     }
 }
@@ -399,6 +728,9 @@ impl std::str::FromStr for KillResult {
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
             pub killed_users_count: Vec<u32>,
+            pub victim: Vec<String>,
+            pub victim_tag: Vec<String>,
+            pub cooldown: Vec<u32>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -416,6 +748,9 @@ impl std::str::FromStr for KillResult {
             if let Some(key) = key_result {
                 match key {
                     "killed_users_count" => intermediate_rep.killed_users_count.push(<u32 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "victim" => intermediate_rep.victim.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "victim_tag" => intermediate_rep.victim_tag.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "cooldown" => intermediate_rep.cooldown.push(<u32 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
                     _ => return std::result::Result::Err("Unexpected key while parsing KillResult".to_string())
                 }
             }
@@ -427,6 +762,9 @@ impl std::str::FromStr for KillResult {
         // Use the intermediate representation to return the struct
         std::result::Result::Ok(KillResult {
             killed_users_count: intermediate_rep.killed_users_count.into_iter().next().ok_or("killed_users_count missing in KillResult".to_string())?,
+            victim: intermediate_rep.victim.into_iter().next(),
+            victim_tag: intermediate_rep.victim_tag.into_iter().next(),
+            cooldown: intermediate_rep.cooldown.into_iter().next().ok_or("cooldown missing in KillResult".to_string())?,
         })
     }
 }
@@ -470,6 +808,188 @@ impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderVal
 }
 
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct Meeting {
+    /// Either discussion or voting
+    #[serde(rename = "phase")]
+    pub phase: String,
+
+    #[serde(rename = "called_by")]
+    pub called_by: String,
+
+    #[serde(rename = "location")]
+    pub location: String,
+
+    /// Whose bodies have been reported, empty for an emergency
+    #[serde(rename = "victims")]
+    pub victims: Vec<String>,
+
+    /// Seconds until the phase ends
+    #[serde(rename = "remaining")]
+    pub remaining: u64,
+
+    /// How many have voted so far
+    #[serde(rename = "votes")]
+    pub votes: u32,
+
+    #[serde(rename = "has_voted")]
+    pub has_voted: bool,
+
+}
+
+impl Meeting {
+    pub fn new(phase: String, called_by: String, location: String, victims: Vec<String>, remaining: u64, votes: u32, has_voted: bool, ) -> Meeting {
+        Meeting {
+            phase: phase,
+            called_by: called_by,
+            location: location,
+            victims: victims,
+            remaining: remaining,
+            votes: votes,
+            has_voted: has_voted,
+        }
+    }
+}
+
+/// Converts the Meeting value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for Meeting {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        params.push("phase".to_string());
+        params.push(self.phase.to_string());
+
+
+        params.push("called_by".to_string());
+        params.push(self.called_by.to_string());
+
+
+        params.push("location".to_string());
+        params.push(self.location.to_string());
+
+
+        params.push("victims".to_string());
+        params.push(self.victims.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",").to_string());
+
+
+        params.push("remaining".to_string());
+        params.push(self.remaining.to_string());
+
+
+        params.push("votes".to_string());
+        params.push(self.votes.to_string());
+
+
+        params.push("has_voted".to_string());
+        params.push(self.has_voted.to_string());
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a Meeting value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for Meeting {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub phase: Vec<String>,
+            pub called_by: Vec<String>,
+            pub location: Vec<String>,
+            pub victims: Vec<Vec<String>>,
+            pub remaining: Vec<u64>,
+            pub votes: Vec<u32>,
+            pub has_voted: Vec<bool>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing Meeting".to_string())
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "phase" => intermediate_rep.phase.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "called_by" => intermediate_rep.called_by.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "location" => intermediate_rep.location.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "victims" => return std::result::Result::Err("Parsing a container in this style is not supported in Meeting".to_string()),
+                    "remaining" => intermediate_rep.remaining.push(<u64 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "votes" => intermediate_rep.votes.push(<u32 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "has_voted" => intermediate_rep.has_voted.push(<bool as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing Meeting".to_string())
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(Meeting {
+            phase: intermediate_rep.phase.into_iter().next().ok_or("phase missing in Meeting".to_string())?,
+            called_by: intermediate_rep.called_by.into_iter().next().ok_or("called_by missing in Meeting".to_string())?,
+            location: intermediate_rep.location.into_iter().next().ok_or("location missing in Meeting".to_string())?,
+            victims: intermediate_rep.victims.into_iter().next().ok_or("victims missing in Meeting".to_string())?,
+            remaining: intermediate_rep.remaining.into_iter().next().ok_or("remaining missing in Meeting".to_string())?,
+            votes: intermediate_rep.votes.into_iter().next().ok_or("votes missing in Meeting".to_string())?,
+            has_voted: intermediate_rep.has_voted.into_iter().next().ok_or("has_voted missing in Meeting".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Meeting> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<Meeting>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Meeting>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+             std::result::Result::Ok(value) => std::result::Result::Ok(value),
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Invalid header value for Meeting - value: {} is invalid {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<Meeting> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+             std::result::Result::Ok(value) => {
+                    match <Meeting as std::str::FromStr>::from_str(value) {
+                        std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                        std::result::Result::Err(err) => std::result::Result::Err(
+                            format!("Unable to convert header value '{}' into Meeting - {}",
+                                value, err))
+                    }
+             },
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Unable to convert header: {:?} to string: {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct MoveTo {
@@ -694,6 +1214,129 @@ impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderVal
 }
 
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct Problem {
+    #[serde(rename = "code")]
+    pub code: String,
+
+    #[serde(rename = "message")]
+    pub message: String,
+
+}
+
+impl Problem {
+    pub fn new(code: String, message: String, ) -> Problem {
+        Problem {
+            code: code,
+            message: message,
+        }
+    }
+}
+
+/// Converts the Problem value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for Problem {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        params.push("code".to_string());
+        params.push(self.code.to_string());
+
+
+        params.push("message".to_string());
+        params.push(self.message.to_string());
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a Problem value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for Problem {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub code: Vec<String>,
+            pub message: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing Problem".to_string())
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "code" => intermediate_rep.code.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "message" => intermediate_rep.message.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing Problem".to_string())
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(Problem {
+            code: intermediate_rep.code.into_iter().next().ok_or("code missing in Problem".to_string())?,
+            message: intermediate_rep.message.into_iter().next().ok_or("message missing in Problem".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Problem> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<Problem>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Problem>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+             std::result::Result::Ok(value) => std::result::Result::Ok(value),
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Invalid header value for Problem - value: {} is invalid {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<Problem> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+             std::result::Result::Ok(value) => {
+                    match <Problem as std::str::FromStr>::from_str(value) {
+                        std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                        std::result::Result::Err(err) => std::result::Result::Err(
+                            format!("Unable to convert header value '{}' into Problem - {}",
+                                value, err))
+                    }
+             },
+             std::result::Result::Err(e) => std::result::Result::Err(
+                 format!("Unable to convert header: {:?} to string: {}",
+                     hdr_value, e))
+        }
+    }
+}
+
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct Query {
@@ -1048,13 +1691,18 @@ pub struct UsersListUsers {
     #[serde(rename = "location")]
     pub location: String,
 
+    /// Tells apart the sessions with the same name
+    #[serde(rename = "tag")]
+    pub tag: String,
+
 }
 
 impl UsersListUsers {
-    pub fn new(user: String, location: String, ) -> UsersListUsers {
+    pub fn new(user: String, location: String, tag: String, ) -> UsersListUsers {
         UsersListUsers {
             user: user,
             location: location,
+            tag: tag,
         }
     }
 }
@@ -1073,6 +1721,10 @@ impl std::string::ToString for UsersListUsers {
         params.push("location".to_string());
         params.push(self.location.to_string());
 
+
+        params.push("tag".to_string());
+        params.push(self.tag.to_string());
+
         params.join(",").to_string()
     }
 }
@@ -1089,6 +1741,7 @@ impl std::str::FromStr for UsersListUsers {
         struct IntermediateRep {
             pub user: Vec<String>,
             pub location: Vec<String>,
+            pub tag: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -1107,6 +1760,7 @@ impl std::str::FromStr for UsersListUsers {
                 match key {
                     "user" => intermediate_rep.user.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
                     "location" => intermediate_rep.location.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
+                    "tag" => intermediate_rep.tag.push(<String as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?),
                     _ => return std::result::Result::Err("Unexpected key while parsing UsersListUsers".to_string())
                 }
             }
@@ -1119,6 +1773,7 @@ impl std::str::FromStr for UsersListUsers {
         std::result::Result::Ok(UsersListUsers {
             user: intermediate_rep.user.into_iter().next().ok_or("user missing in UsersListUsers".to_string())?,
             location: intermediate_rep.location.into_iter().next().ok_or("location missing in UsersListUsers".to_string())?,
+            tag: intermediate_rep.tag.into_iter().next().ok_or("tag missing in UsersListUsers".to_string())?,
         })
     }
 }
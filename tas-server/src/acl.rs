@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use common::serializable;
+use common::{Result};
+
+use crate::server::{SusContext, UserData};
+
+serializable! {
+    /// Where the members of a role may go. The paths are
+    /// the ones seen inside the jail, like `/cafeteria`.
+    #[derive(Default)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct PathRules {
+        /// Only these subtrees are
+        /// reachable, anywhere if empty.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub roots: Vec<String>,
+        /// May be looked at, but not changed.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub read_only: Vec<String>,
+        /// May not be entered at all.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub forbidden: Vec<String>,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl PathRules {
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty() && self.read_only.is_empty() && self.forbidden.is_empty()
+    }
+
+    /// Adds the `other` rules on top of these ones.
+    /// Non-empty `roots` replace the current ones
+    /// when `is_own`, and add up otherwise.
+    pub fn extend(&mut self, other: &PathRules, is_own: bool) {
+        if is_own && !other.roots.is_empty() {
+            self.roots.clear();
+        }
+
+        self.roots.extend(other.roots.iter().cloned());
+        self.read_only.extend(other.read_only.iter().cloned());
+        self.forbidden.extend(other.forbidden.iter().cloned());
+    }
+
+    /// `location` is the one seen inside the jail.
+    pub fn allows(&self, location: &str, access: Access) -> bool {
+        let location = Path::new(location);
+        let is_within = |it: &String| location.starts_with(subtree(it));
+
+        if !self.roots.is_empty() && !self.roots.iter().any(is_within) {
+            return false
+        }
+
+        if self.forbidden.iter().any(is_within) {
+            return false
+        }
+
+        access == Access::Read || !self.read_only.iter().any(is_within)
+    }
+}
+
+/// Both `admin` and `/admin/`
+/// mean the same subtree.
+fn subtree(path: &str) -> PathBuf {
+    Path::new("/").join(path)
+}
+
+/// The rules of the role `me` currently has.
+pub fn rules_for(context: &SusContext, me: &UserData) -> Result<PathRules> {
+    let role = context.members.read()?.role_of(me, &context.command_names)?;
+    Ok(role.paths)
+}
+
+/// Whether the role of `me` lets them
+/// reach the real `location`.
+pub fn may_access(context: &SusContext, me: &UserData, location: &Path, access: Access) -> Result<bool> {
+    let rules = rules_for(context, me)?;
+    Ok(rules.allows(&context.jail.to_virtual(location), access))
+}
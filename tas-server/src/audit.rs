@@ -0,0 +1,528 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Instant};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::{oneshot};
+
+use openapi_client::{
+    GetEventsResponse,
+    GetMeetingResponse,
+    GetMyselfResponse,
+    GetNewUserResponse,
+    PostQueryResponse,
+};
+
+use common::serializable;
+use common::{ErrorKind, Result, with_error_report};
+
+use crate::server::{SusContext};
+
+pub const AUDIT_FILE: &str = "audit.jsonl";
+pub const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_KEEP: u32 = 5;
+
+/// How many entries the `audit`
+/// command shows by default.
+pub const DEFAULT_LIMIT: usize = 20;
+
+serializable! {
+    /// A single API call, one per line of the log.
+    pub struct AuditRecord {
+        pub timestamp: DateTime<Utc>,
+        /// The `X-Span-ID` of the request, if
+        /// it came via the HTTP API.
+        pub span_id: Option<String>,
+        /// The API operation, like `post_query`, or what the
+        /// server has decided, like `lockout` or `unlock`.
+        pub call: String,
+        pub identity: Option<String>,
+        /// For the decisions, the member
+        /// locked out or unlocked.
+        pub member: Option<String>,
+        pub role: Option<String>,
+        pub location: Option<String>,
+        /// With the secrets hidden,
+        /// see `Commands::redact()`.
+        pub argv: Vec<String>,
+        /// The response variant, like
+        /// `YouMayNotDoThat`.
+        pub outcome: String,
+        pub latency_ms: u64,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditOptions {
+    pub path: PathBuf,
+    /// The file is rotated once it
+    /// would grow past this size.
+    pub max_size: u64,
+    /// The number of the rotated files to keep
+    /// next to the current one, as `.1`, `.2`...
+    pub keep: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AuditFilter {
+    pub member: Option<String>,
+    pub command: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn accepts(&self, record: &AuditRecord) -> bool {
+        if self.member.is_some() && record.member != self.member {
+            return false
+        }
+
+        if let Some(it) = &self.command {
+            if record.argv.first() != Some(it) {
+                return false
+            }
+        }
+
+        if self.since.is_some_and(|it| record.timestamp < it) {
+            return false
+        }
+
+        if self.until.is_some_and(|it| record.timestamp > it) {
+            return false
+        }
+
+        true
+    }
+}
+
+/// Appends the records as JSON lines
+/// and rotates the file by size.
+pub struct AuditLog {
+    options: Option<AuditOptions>,
+    file: Option<File>,
+    size: u64,
+}
+
+fn rotated(path: &Path, index: u32) -> PathBuf {
+    let mut it = path.as_os_str().to_owned();
+    it.push(format!(".{}", index));
+    PathBuf::from(it)
+}
+
+impl AuditLog {
+    pub fn new(options: AuditOptions) -> Self {
+        AuditLog {
+            options: Some(options),
+            file: None,
+            size: 0,
+        }
+    }
+
+    /// Drops the records.
+    pub fn disabled() -> Self {
+        AuditLog {
+            options: None,
+            file: None,
+            size: 0,
+        }
+    }
+
+    pub fn append(&mut self, record: &AuditRecord) -> Result<()> {
+        let options = match &self.options {
+            Some(it) => it.clone(),
+            None => return Ok(()),
+        };
+
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.file.is_none() {
+            let file = OpenOptions::new().create(true).append(true).open(&options.path)?;
+            self.size = file.metadata()?.len();
+            self.file = Some(file);
+        }
+
+        if self.size > 0 && self.size + line.len() as u64 > options.max_size {
+            self.rotate(&options)?;
+        }
+
+        if let Some(file) = &mut self.file {
+            file.write_all(&line)?;
+            self.size += line.len() as u64;
+        }
+
+        Ok(())
+    }
+
+    fn rotate(&mut self, options: &AuditOptions) -> Result<()> {
+        self.file = None;
+
+        if options.keep == 0 {
+            std::fs::remove_file(&options.path)?;
+        } else {
+            for index in (1..options.keep).rev() {
+                let older = rotated(&options.path, index);
+
+                if older.exists() {
+                    std::fs::rename(&older, rotated(&options.path, index + 1))?;
+                }
+            }
+
+            std::fs::rename(&options.path, rotated(&options.path, 1))?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&options.path)?;
+        self.file = Some(file);
+        self.size = 0;
+
+        Ok(())
+    }
+
+    /// `None` if the log is disabled.
+    pub fn options(&self) -> Option<AuditOptions> {
+        self.options.clone()
+    }
+}
+
+enum Message {
+    Record(Box<AuditRecord>),
+    /// Answered once everything
+    /// before it is written.
+    Flush(oneshot::Sender<()>),
+}
+
+/// Hands the records over to a thread owning the
+/// `AuditLog`, so that the calls only wait for the
+/// queue rather than for the disk.
+#[derive(Clone)]
+pub struct AuditWriter {
+    messages: Sender<Message>,
+    options: Option<AuditOptions>,
+}
+
+impl AuditWriter {
+    pub fn spawn(log: AuditLog) -> Self {
+        let options = log.options();
+        let (messages, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || write_records(log, receiver));
+
+        AuditWriter {
+            messages,
+            options,
+        }
+    }
+
+    pub fn append(&self, record: AuditRecord) -> Result<()> {
+        match self.messages.send(Message::Record(Box::new(record))) {
+            Ok(_) => Ok(()),
+            Err(_) => ErrorKind::SendError {
+                message: "The audit writer is not running".to_owned()
+            }.into(),
+        }
+    }
+
+    /// Resolves once the records
+    /// sent so far are written.
+    pub async fn flush(&self) {
+        let (done, written) = oneshot::channel();
+
+        if self.messages.send(Message::Flush(done)).is_ok() {
+            let _ = written.await;
+        }
+    }
+
+    /// `None` if the log is disabled.
+    pub fn options(&self) -> Option<AuditOptions> {
+        self.options.clone()
+    }
+}
+
+fn write_records(mut log: AuditLog, messages: Receiver<Message>) {
+    for it in messages {
+        match it {
+            Message::Record(record) => with_error_report(|| log.append(&record)),
+            Message::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+/// The last `limit` records passing the `filter`, the
+/// oldest first. Looks into the rotated files as well.
+pub fn query(options: &AuditOptions, filter: &AuditFilter, limit: usize) -> Result<Vec<AuditRecord>> {
+    let mut found = vec![];
+    let mut paths = vec![options.path.clone()];
+    paths.extend((1..=options.keep).map(|it| rotated(&options.path, it)));
+
+    for path in paths {
+        if found.len() >= limit {
+            break
+        }
+
+        // May also be rotated away while reading
+        let file = match File::open(&path) {
+            Ok(it) => it,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => break,
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut records = vec![];
+
+        for line in BufReader::new(file).lines() {
+            // Skips what a crash or an append
+            // in progress may have cut short
+            if let Ok(it) = serde_json::from_str::<AuditRecord>(&line?) {
+                if filter.accepts(&it) {
+                    records.push(it);
+                }
+            }
+        }
+
+        found.extend(records.into_iter().rev().take(limit - found.len()));
+    }
+
+    found.reverse();
+    Ok(found)
+}
+
+/// Like `query()`, but on the blocking pool,
+/// so the appends and the calls go on.
+pub async fn query_in_background(
+    context: &SusContext,
+    filter: AuditFilter,
+    limit: usize,
+) -> Result<Vec<AuditRecord>> {
+    let options = match context.audit.options() {
+        Some(it) => it,
+        None => return Ok(vec![]),
+    };
+
+    // So that the latest calls are seen
+    context.audit.flush().await;
+
+    match tokio::task::spawn_blocking(move || query(&options, &filter, limit)).await {
+        Ok(it) => it,
+        Err(error) => Err(std::io::Error::other(format!("{}", error)).into()),
+    }
+}
+
+/// Everything known about an API call
+/// before it's handled.
+pub struct Call {
+    pub span_id: Option<String>,
+    pub call: &'static str,
+    pub identity: Option<String>,
+    pub argv: Vec<String>,
+    pub member: Option<String>,
+    pub role: Option<String>,
+    pub location: Option<String>,
+    pub started_at: Instant,
+}
+
+impl Call {
+    /// Looks up who makes the call and from where
+    /// before it's handled, since `cd`, `login` and
+    /// the like change that.
+    pub async fn start(
+        context: &SusContext,
+        span_id: Option<String>,
+        call: &'static str,
+        identity: Option<String>,
+        argv: Vec<String>,
+    ) -> Self {
+        let mut it = Call {
+            span_id,
+            call,
+            identity,
+            argv,
+            member: None,
+            role: None,
+            location: None,
+            started_at: Instant::now(),
+        };
+
+        if let Some(identity) = it.identity.clone() {
+            let result = it.look_up_caller(context, identity).await;
+            with_error_report(|| result);
+        }
+
+        it
+    }
+
+    async fn look_up_caller(&mut self, context: &SusContext, identity: String) -> Result<()> {
+        let user = match context.game.call(move |state| state.sessions.get(&identity).cloned()).await? {
+            Some(it) => it,
+            None => return Ok(()),
+        };
+
+        if let Ok(it) = context.members.read()?.role_of(&user, &context.command_names) {
+            self.role = Some(it.title);
+        }
+
+        self.location = Some(context.jail.to_virtual(&user.location));
+        self.member = Some(user.name);
+
+        Ok(())
+    }
+
+    pub fn finish(self, outcome: String) -> AuditRecord {
+        AuditRecord {
+            timestamp: Utc::now(),
+            span_id: self.span_id,
+            call: self.call.to_owned(),
+            identity: self.identity,
+            member: self.member,
+            role: self.role,
+            location: self.location,
+            argv: self.argv,
+            outcome,
+            latency_ms: self.started_at.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// The name of the response variant, like `WhoAreYou`.
+pub trait Outcome {
+    fn outcome(&self) -> &'static str;
+}
+
+impl Outcome for GetEventsResponse {
+    fn outcome(&self) -> &'static str {
+        match self {
+            GetEventsResponse::HereIsWhatHappened(_) => "HereIsWhatHappened",
+            GetEventsResponse::YourRequestIsJunk(_) => "YourRequestIsJunk",
+            GetEventsResponse::WhoAreYou(_) => "WhoAreYou",
+            GetEventsResponse::YourSessionHasExpired => "YourSessionHasExpired",
+            GetEventsResponse::SomethingWentWrong(_) => "SomethingWentWrong",
+        }
+    }
+}
+
+impl Outcome for GetMeetingResponse {
+    fn outcome(&self) -> &'static str {
+        match self {
+            GetMeetingResponse::HereIsTheMeeting(_) => "HereIsTheMeeting",
+            GetMeetingResponse::ThereIsNoMeeting => "ThereIsNoMeeting",
+            GetMeetingResponse::YourRequestIsJunk(_) => "YourRequestIsJunk",
+            GetMeetingResponse::WhoAreYou(_) => "WhoAreYou",
+            GetMeetingResponse::YourSessionHasExpired => "YourSessionHasExpired",
+            GetMeetingResponse::SomethingWentWrong(_) => "SomethingWentWrong",
+        }
+    }
+}
+
+impl Outcome for GetMyselfResponse {
+    fn outcome(&self) -> &'static str {
+        match self {
+            GetMyselfResponse::HereIsYourStatus(_) => "HereIsYourStatus",
+            GetMyselfResponse::YourRequestIsJunk(_) => "YourRequestIsJunk",
+            GetMyselfResponse::WhoAreYou(_) => "WhoAreYou",
+            GetMyselfResponse::SomethingWentWrong(_) => "SomethingWentWrong",
+        }
+    }
+}
+
+impl Outcome for GetNewUserResponse {
+    fn outcome(&self) -> &'static str {
+        match self {
+            GetNewUserResponse::HereIsTheDefaultIdentity(_) => "HereIsTheDefaultIdentity",
+            GetNewUserResponse::YourRequestIsJunk(_) => "YourRequestIsJunk",
+            GetNewUserResponse::SomethingWentWrong(_) => "SomethingWentWrong",
+        }
+    }
+}
+
+impl Outcome for PostQueryResponse {
+    fn outcome(&self) -> &'static str {
+        match self {
+            PostQueryResponse::HereIsTheKillResult(_) => "HereIsTheKillResult",
+            PostQueryResponse::HereIsANewRoleForYou(_) => "HereIsANewRoleForYou",
+            PostQueryResponse::HereAreTheFiles(_) => "HereAreTheFiles",
+            PostQueryResponse::HereAreYourCrewmates(_) => "HereAreYourCrewmates",
+            PostQueryResponse::SomeRandomInformation(_) => "SomeRandomInformation",
+            PostQueryResponse::HereIsTheNewLocation(_) => "HereIsTheNewLocation",
+            PostQueryResponse::YourRequestIsJunk(_) => "YourRequestIsJunk",
+            PostQueryResponse::WhoAreYou(_) => "WhoAreYou",
+            PostQueryResponse::YouMayNotDoThat(_) => "YouMayNotDoThat",
+            PostQueryResponse::YourSessionHasExpired => "YourSessionHasExpired",
+            PostQueryResponse::SomethingWentWrong(_) => "SomethingWentWrong",
+        }
+    }
+}
+
+pub fn outcome_of<T: Outcome>(response: &T) -> String {
+    response.outcome().to_owned()
+}
+
+/// Appends the record of the `call`. Failures are
+/// only reported, since the call itself is over by now.
+pub fn record(context: &SusContext, call: Call, outcome: String) {
+    let record = call.finish(outcome);
+    with_error_report(|| context.audit.append(record));
+}
+
+/// Appends the record of a decision about the `member`
+/// rather than of an API call, like a lockout. Failures
+/// are only reported, like in `record()`.
+pub fn record_decision(
+    context: &SusContext,
+    call: &str,
+    identity: Option<String>,
+    member: Option<String>,
+    outcome: &str,
+) {
+    let record = AuditRecord {
+        timestamp: Utc::now(),
+        span_id: None,
+        call: call.to_owned(),
+        identity,
+        member,
+        role: None,
+        location: None,
+        argv: vec![],
+        outcome: outcome.to_owned(),
+        latency_ms: 0,
+    };
+
+    with_error_report(|| context.audit.append(record));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::{Path};
+
+    use crate::commands::{Commands};
+    use crate::jail::{Jail};
+    use crate::members::{Members};
+    use crate::server::{new_context, GameRules};
+    use crate::sessions::{start_guest_session};
+
+    #[tokio::test]
+    async fn records_where_the_call_came_from() {
+        let members: Members = serde_json::from_str(r#"{
+            "roles": {"ghost": ["cd"], "wanderer": ["cd"]},
+            "users": {"guest": {"role": "wanderer", "pass": ""}}
+        }"#).unwrap();
+
+        let commands = Commands::builtin();
+        let jail = Jail::new(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let context = new_context(jail, GameRules::default(), AuditLog::disabled(), members, None, &commands);
+        let me = start_guest_session(&context).await.unwrap();
+
+        let argv = vec!["cd".to_owned(), "src".to_owned()];
+        let call = Call::start(&context, None, "post_query", Some(me.identity.clone()), argv.clone()).await;
+        let response = commands.execute(argv, &context, me).await.unwrap();
+        let record = call.finish(outcome_of(&response));
+
+        assert_eq!(record.outcome, "HereIsTheNewLocation");
+        assert_eq!(record.member.as_deref(), Some("guest"));
+        assert_eq!(record.role.as_deref(), Some("wanderer"));
+        assert_eq!(record.location.as_deref(), Some("/"));
+    }
+}
@@ -0,0 +1,44 @@
+use clap::{ArgMatches};
+
+use crate::commands::{Commands};
+use crate::config::{Config};
+use crate::members::{read_members};
+
+/// Everything wrong with the settings and the members
+/// they point at, each prefixed with the file in question.
+/// Nothing is written, so it's safe to run before deploys.
+pub fn find_problems(matches: &ArgMatches, commands: &Commands) -> Vec<String> {
+    let config = match Config::read_matches(matches) {
+        Ok(it) => it,
+        Err(error) => return vec![format!("{}", error)],
+    };
+
+    let source = matches.value_of("config").unwrap_or("<flags>");
+
+    let mut problems: Vec<String> = config.problems().into_iter()
+        .map(|it| format!("{}: {}", source, it))
+        .collect();
+
+    if !config.members.is_file() {
+        return problems
+    }
+
+    let file = config.members.display();
+
+    let members = match read_members(&config.members) {
+        Ok(it) => it,
+        Err(error) => {
+            problems.push(format!("{}: {}", file, error));
+            return problems
+        }
+    };
+
+    let found = members.problems(&commands.qualified_names()).into_iter()
+        .chain(members.password_problems());
+
+    for it in found {
+        problems.push(format!("{}: {}", file, it));
+    }
+
+    problems
+}
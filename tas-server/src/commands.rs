@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+use std::collections::{HashMap};
+use std::sync::{Arc};
+use std::time::{Duration};
+
+use openapi_client::models;
+use openapi_client::{PostQueryResponse};
+
+use crate::members::{ADMIN_GROUP};
+use crate::problems::{Failure};
+use crate::server::{SusContext, UserData};
+
+pub mod admin;
+pub mod announce;
+pub mod login;
+pub mod navigation;
+pub mod who;
+pub mod kill;
+pub mod meeting;
+pub mod report;
+pub mod round;
+pub mod tasks;
+
+pub type CommandResult = std::result::Result<PostQueryResponse, Failure>;
+
+pub fn notify(message: &str) -> models::Notification {
+    models::Notification {
+        message: message.to_owned()
+    }
+}
+
+/// What the file commands say when
+/// the role keeps the user out.
+pub fn access_denied(location: &str) -> Failure {
+    Failure::forbidden("access_denied", &format!("Access denied > {}", location))
+}
+
+/// Rounded up, so that 0 only
+/// means there's no waiting at all.
+pub fn whole_seconds(duration: Duration) -> u64 {
+    duration.as_millis().div_ceil(1000) as u64
+}
+
+/// What moving, killing and calling meetings
+/// answer while everyone is meeting.
+pub fn meeting_going_on() -> Failure {
+    Failure::forbidden("meeting", "There's a meeting going on")
+}
+
+/// Several sessions share the `name`, so the
+/// tag of the one meant is needed.
+pub fn ambiguous_target(name: &str, candidates: &[UserData]) -> Failure {
+    let tags: Vec<String> = candidates.iter().map(|it| it.tag.clone()).collect();
+    let message = format!("There are several sessions named {}, add one of the tags > {}", name, tags.join(", "));
+    Failure::bad_request("ambiguous_target", &message)
+}
+
+/// A single terminal command that can be run via `post_query`.
+/// The arguments passed to `handle` include the command
+/// name itself at index 0, and `me` is a snapshot of the
+/// user taken right before the call. Changes to the game
+/// state go through `context.game`.
+#[async_trait]
+pub trait Command: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Lets the roles allow related
+    /// commands at once, like `fs.*`.
+    fn group(&self) -> &str {
+        "game"
+    }
+
+    fn usage(&self) -> &str;
+
+    /// Indices of the arguments to hide from the
+    /// audit log, like passwords. The command
+    /// name is at index 0.
+    fn secret_arguments(&self) -> &[usize] {
+        &[]
+    }
+
+    /// The number of arguments required
+    /// (not counting the command name).
+    fn minimum_arity(&self) -> usize {
+        0
+    }
+
+    /// Whether it still runs once either team
+    /// has won, so that the admins can start
+    /// a new round.
+    fn runs_after_game_over(&self) -> bool {
+        self.group() == ADMIN_GROUP
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult;
+}
+
+#[derive(Clone)]
+pub struct Commands {
+    handlers: HashMap<String, Arc<dyn Command>>,
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Commands::new()
+    }
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Commands {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// The commands every Amogus Terminal
+    /// is expected to have.
+    pub fn builtin() -> Self {
+        let mut it = Commands::new();
+        it.register(login::Login);
+        it.register(navigation::Ls);
+        it.register(navigation::Cd);
+        it.register(who::Who);
+        it.register(kill::Kill);
+        it.register(report::Report);
+        it.register(meeting::Emergency);
+        it.register(meeting::Vote);
+        it.register(meeting::Skip);
+        it.register(tasks::Tasks);
+        it.register(announce::Announce);
+        it.register(round::NewRound);
+        it.register(admin::UserAdd);
+        it.register(admin::UserDel);
+        it.register(admin::Passwd);
+        it.register(admin::RoleAdd);
+        it.register(admin::RoleDel);
+        it.register(admin::Grant);
+        it.register(admin::Revoke);
+        it.register(admin::Unlock);
+        it.register(admin::Audit);
+        it
+    }
+
+    /// Adds a new command or replaces the
+    /// existing one with the same name.
+    pub fn register<T: Command + 'static>(&mut self, command: T) {
+        self.handlers.insert(command.name().to_owned(), Arc::new(command));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Command>> {
+        self.handlers.get(name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.handlers.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// The names prefixed with the groups,
+    /// like `fs.ls`, as the roles see them.
+    pub fn qualified_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.handlers.values()
+            .map(|it| format!("{}.{}", it.group(), it.name()))
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    /// Hides the secret arguments, see
+    /// `Command::secret_arguments()`.
+    pub fn redact(&self, mut command: Vec<String>) -> Vec<String> {
+        let handler = match command.first().and_then(|it| self.get(it)) {
+            Some(it) => it,
+            None => return command,
+        };
+
+        for index in handler.secret_arguments() {
+            if let Some(it) = command.get_mut(*index) {
+                *it = "***".to_owned();
+            }
+        }
+
+        command
+    }
+
+    /// Checks the user is allowed to run the
+    /// command and passes it to the handler.
+    pub async fn execute(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        mut me: UserData,
+    ) -> CommandResult {
+        let runs_anyway = self.get(&command[0]).is_some_and(|it| it.runs_after_game_over());
+
+        match context.game.call(|state| state.game_over).await {
+            Ok(Some(it)) if !runs_anyway => return Err(Failure::forbidden(
+                "game_over", &format!("The game is over, the winners are the {}", it.as_str())
+            )),
+            Ok(_) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let (role, is_member) = match context.members.read() {
+            Ok(it) => {
+                // The members may have changed since the login
+                let is_member = it.has_user(&me.name);
+
+                if !is_member {
+                    me.name = "guest".to_owned();
+                }
+
+                match it.role_of(&me, &context.command_names) {
+                    Ok(that) => (that, is_member),
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        if !is_member {
+            let identity = me.identity.clone();
+            let result = context.game.call(move |state| state.sessions.rename(&identity, "guest")).await;
+
+            if let Err(error) = result {
+                return Err(error.into())
+            }
+        }
+
+        if !role.allowed_commands.contains(&command[0]) {
+            return Err(Failure::forbidden("command_not_allowed", "No such an allowed command for you"))
+        }
+
+        let handler = match self.get(&command[0]) {
+            Some(it) => it,
+            None => return Err(Failure::bad_request(
+                "unknown_command", &format!("No such a command > {}", command[0])
+            ))
+        };
+
+        if command.len() - 1 < handler.minimum_arity() {
+            return Err(Failure::bad_request(
+                "missing_arguments", &format!("The command misses some parameters > {}", handler.usage())
+            ))
+        }
+
+        handler.handle(command, context, me).await
+    }
+}
@@ -0,0 +1,427 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{warn};
+
+use openapi_client::{PostQueryResponse};
+
+use crate::audit::{self, AuditFilter, DEFAULT_LIMIT};
+use crate::commands::{Command, CommandResult, notify};
+use crate::members::{save_members, Members};
+use crate::passwords::{hash_password_in_background};
+use crate::problems::{Failure};
+use crate::reload::{modified_at};
+use crate::server::{SusContext, UserData};
+
+/// Applies the `change` to a copy of the members. If the
+/// result is still usable, it's saved and swapped in,
+/// otherwise nothing changes and the admin is told why.
+async fn update_members<F>(context: &SusContext, done: &str, change: F) -> CommandResult
+where
+    F: FnOnce(&mut Members) -> std::result::Result<(), String>,
+{
+    // The role lookups go on while the file is saved
+    let _changing = context.members_changing.lock().await;
+    let mut members = context.members.read()?.clone();
+
+    let result = change(&mut members)
+        .and_then(|_| members.check(&context.command_names));
+
+    if let Err(message) = result {
+        return Err(Failure::bad_request("refused", &format!("Refused > {}", message)))
+    }
+
+    if let Some(path) = &context.members_file {
+        if let Err(error) = save_members(&members, path) {
+            return Err(error.into())
+        }
+
+        // Not to be reloaded as if someone else changed it
+        *context.members_saved_at.write()? = modified_at(path);
+    }
+
+    *context.members.write()? = members;
+
+    Ok(PostQueryResponse::SomeRandomInformation(notify(done)))
+}
+
+async fn hash(pass: &str) -> std::result::Result<String, Failure> {
+    match hash_password_in_background(pass.to_owned()).await {
+        Ok(it) => Ok(it),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub struct UserAdd;
+
+#[async_trait]
+impl Command for UserAdd {
+    fn name(&self) -> &str {
+        "useradd"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "useradd <name> <password> <role>"
+    }
+
+    fn secret_arguments(&self) -> &[usize] {
+        &[2]
+    }
+
+    fn minimum_arity(&self) -> usize {
+        3
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let name = &command[1];
+        let pass = hash(&command[2]).await?;
+        let role = &command[3];
+
+        let done = format!("Added {} as {}", name, role);
+        update_members(context, &done, |it| it.add_user(name, pass, role)).await
+    }
+}
+
+pub struct UserDel;
+
+#[async_trait]
+impl Command for UserDel {
+    fn name(&self) -> &str {
+        "userdel"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "userdel <name>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let name = &command[1];
+
+        let done = format!("Removed {}", name);
+        update_members(context, &done, |it| it.remove_user(name)).await
+    }
+}
+
+pub struct Passwd;
+
+#[async_trait]
+impl Command for Passwd {
+    fn name(&self) -> &str {
+        "passwd"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "passwd <name> <password>"
+    }
+
+    fn secret_arguments(&self) -> &[usize] {
+        &[2]
+    }
+
+    fn minimum_arity(&self) -> usize {
+        2
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let name = &command[1];
+        let pass = hash(&command[2]).await?;
+
+        let done = format!("Changed the password of {}", name);
+        update_members(context, &done, |it| it.set_password(name, pass)).await
+    }
+}
+
+pub struct RoleAdd;
+
+#[async_trait]
+impl Command for RoleAdd {
+    fn name(&self) -> &str {
+        "roleadd"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "roleadd <role> [<command>...]"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let title = &command[1];
+        let allowed = command[2..].to_vec();
+
+        let done = format!("Added the role {}", title);
+        update_members(context, &done, |it| it.add_role(title, allowed)).await
+    }
+}
+
+pub struct RoleDel;
+
+#[async_trait]
+impl Command for RoleDel {
+    fn name(&self) -> &str {
+        "roledel"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "roledel <role>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let title = &command[1];
+
+        let done = format!("Removed the role {}", title);
+        update_members(context, &done, |it| it.remove_role(title)).await
+    }
+}
+
+pub struct Grant;
+
+#[async_trait]
+impl Command for Grant {
+    fn name(&self) -> &str {
+        "grant"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "grant <role> <command>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        2
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let title = &command[1];
+        let allowed = &command[2];
+
+        let done = format!("The role {} may now {}", title, allowed);
+        update_members(context, &done, |it| it.grant(title, allowed)).await
+    }
+}
+
+pub struct Revoke;
+
+#[async_trait]
+impl Command for Revoke {
+    fn name(&self) -> &str {
+        "revoke"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "revoke <role> <command>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        2
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let title = &command[1];
+        let denied = &command[2];
+
+        let done = format!("The role {} may no longer {}", title, denied);
+        update_members(context, &done, |it| it.revoke(title, denied)).await
+    }
+}
+
+pub struct Unlock;
+
+#[async_trait]
+impl Command for Unlock {
+    fn name(&self) -> &str {
+        "unlock"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "unlock <name>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let name = &command[1];
+
+        let was_locked = match context.lockout.write() {
+            Ok(mut it) => it.unlock(name),
+            Err(error) => return Err(error.into())
+        };
+
+        if !was_locked {
+            return Ok(PostQueryResponse::SomeRandomInformation(
+                notify(&format!("No failed logins to forget for {}", name))
+            ))
+        }
+
+        warn!(target: "audit", "{} unlocked the member {}", me.name, name);
+        audit::record_decision(context, "unlock", Some(me.identity.clone()), Some(name.clone()), "Unlocked");
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify(&format!("Unlocked {}", name))))
+    }
+}
+
+fn parse_time(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(it) => Ok(it.with_timezone(&Utc)),
+        Err(_) => Err(format!("Not an RFC 3339 time > {}", value)),
+    }
+}
+
+fn parse_filter(arguments: &[String]) -> std::result::Result<(AuditFilter, usize), String> {
+    let mut filter = AuditFilter::default();
+    let mut limit = DEFAULT_LIMIT;
+
+    for it in arguments {
+        let mut parts = it.splitn(2, '=');
+        let key = parts.next().unwrap_or("");
+        let value = parts.next().unwrap_or("");
+
+        match key {
+            "member" => filter.member = Some(value.to_owned()),
+            "command" => filter.command = Some(value.to_owned()),
+            "since" => filter.since = Some(parse_time(value)?),
+            "until" => filter.until = Some(parse_time(value)?),
+            "limit" => match value.parse() {
+                Ok(that) => limit = that,
+                Err(_) => return Err(format!("Not a number > {}", value)),
+            },
+            _ => return Err(format!("Unknown filter > {}", it)),
+        }
+    }
+
+    Ok((filter, limit))
+}
+
+/// Shows the recent audit records as JSON lines.
+pub struct Audit;
+
+#[async_trait]
+impl Command for Audit {
+    fn name(&self) -> &str {
+        "audit"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "audit [member=<name>] [command=<name>] [since=<time>] [until=<time>] [limit=<count>]"
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let (filter, limit) = match parse_filter(&command[1..]) {
+            Ok(it) => it,
+            Err(message) => return Err(Failure::bad_request(
+                "bad_arguments", &format!("{} > {}", message, self.usage())
+            )),
+        };
+
+        let records = match audit::query_in_background(context, filter, limit).await {
+            Ok(it) => it,
+            Err(error) => return Err(error.into()),
+        };
+
+        if records.is_empty() {
+            return Ok(PostQueryResponse::SomeRandomInformation(notify("No matching records")))
+        }
+
+        let mut lines = vec![];
+
+        for it in &records {
+            match serde_json::to_string(it) {
+                Ok(that) => lines.push(that),
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify(&lines.join("\n"))))
+    }
+}
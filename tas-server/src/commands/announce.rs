@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, notify};
+use crate::events::{EventKind};
+use crate::server::{SusContext, UserData};
+
+/// Sends a message to everyone on the ship.
+pub struct Announce;
+
+#[async_trait]
+impl Command for Announce {
+    fn name(&self) -> &str {
+        "announce"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "announce <message>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let message = command[1..].join(" ");
+
+        if let Err(error) = context.events.broadcast(EventKind::Announcement, &message) {
+            return Err(error.into())
+        }
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify("Announced")))
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{Utc};
+
+use openapi_client::models;
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, ambiguous_target, meeting_going_on, whole_seconds};
+use crate::events::{EventKind};
+use crate::meetings::{Body};
+use crate::members::{GHOST_ROLE};
+use crate::problems::{Failure};
+use crate::server::{Death, SusContext, UserData};
+
+fn cooldown_left(me: &UserData, cooldown: Duration) -> Duration {
+    match me.last_kill {
+        Some(it) => cooldown.checked_sub(it.elapsed()).unwrap_or(Duration::from_secs(0)),
+        None => Duration::from_secs(0),
+    }
+}
+
+fn nobody_killed(cooldown: Duration) -> PostQueryResponse {
+    PostQueryResponse::HereIsTheKillResult(models::KillResult {
+        killed_users_count: 0,
+        victim: None,
+        victim_tag: None,
+        cooldown: whole_seconds(cooldown) as u32,
+    })
+}
+
+pub struct Kill;
+
+#[async_trait]
+impl Command for Kill {
+    fn name(&self) -> &str {
+        "kill"
+    }
+
+    fn usage(&self) -> &str {
+        "kill <name> [<tag>]"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let target = command[1].clone();
+        let tag = command.get(2).cloned();
+        let my_identity = me.identity.clone();
+
+        // Someone may have killed us since
+        // the command has arrived
+        let result = context.game.call(move |state| {
+            let me = match state.sessions.get(&my_identity) {
+                Some(it) if it.is_ghost() => return Err(Failure::forbidden("ghost", "Ghosts can't kill")),
+                Some(it) => it.clone(),
+                None => return Err(Failure::unknown_identity()),
+            };
+
+            if state.current_meeting().is_some() {
+                return Err(meeting_going_on())
+            }
+
+            let nearby = state.sessions.living_named_at(&target, &me.location);
+            Ok((me, nearby))
+        }).await;
+
+        let (me, nearby) = match result {
+            Ok(Ok(it)) => it,
+            Ok(Err(failure)) => return Err(failure),
+            Err(error) => return Err(error.into())
+        };
+
+        let (cooldown, teammates, mut candidates) = {
+            let members = match context.members.read() {
+                Ok(it) => it,
+                Err(error) => return Err(error.into())
+            };
+
+            let my_role = match members.role_of(&me, &context.command_names) {
+                Ok(it) => it,
+                Err(error) => return Err(error.into())
+            };
+
+            let is_teammate = |it: &UserData| {
+                members.role_of(it, &context.command_names).is_ok_and(|that| that.title == my_role.title)
+            };
+
+            let (teammates, others): (Vec<_>, Vec<_>) = nearby.into_iter()
+                .filter(|it| tag.as_ref().is_none_or(|that| &it.tag == that))
+                .partition(|it| it.identity != me.identity && is_teammate(it));
+
+            (my_role.kill_cooldown, teammates, others)
+        };
+
+        let left = cooldown_left(&me, cooldown);
+
+        if left > Duration::from_secs(0) {
+            return Ok(nobody_killed(left))
+        }
+
+        if candidates.iter().any(|it| it.identity == me.identity) {
+            if candidates.len() == 1 && teammates.is_empty() {
+                return Err(Failure::bad_request("self_kill", "You can't kill yourself"))
+            }
+
+            candidates.retain(|it| it.identity != me.identity);
+        }
+
+        if candidates.is_empty() && !teammates.is_empty() {
+            return Err(Failure::forbidden("teammate", "You can't kill your teammates"))
+        }
+
+        if candidates.len() > 1 {
+            return Err(ambiguous_target(&command[1], &candidates))
+        }
+
+        let victim = match candidates.pop() {
+            Some(it) => it,
+            None => return Ok(nobody_killed(left)),
+        };
+
+        let death = Death {
+            killer: me.name.clone(),
+            killed_at: Utc::now(),
+        };
+
+        let my_identity = me.identity.clone();
+        let victim_identity = victim.identity.clone();
+
+        // Either of us may have moved or died since the
+        // lookup, and a kill of ours running alongside may
+        // have started the cooldown. Fails with what's left
+        let result = context.game.call(move |state| {
+            let left = match (state.sessions.get(&my_identity), state.sessions.get(&victim_identity)) {
+                (Some(killer), Some(victim)) if !killer.is_ghost() && killer.location == victim.location => {
+                    cooldown_left(killer, cooldown)
+                }
+                _ => return Err(Duration::from_secs(0)),
+            };
+
+            if left > Duration::from_secs(0) || state.current_meeting().is_some() {
+                return Err(left)
+            }
+
+            let victim = match state.sessions.kill(&victim_identity, &death) {
+                Some(it) => it,
+                None => return Err(left),
+            };
+
+            state.sessions.mark_kill(&my_identity, Instant::now());
+
+            state.bodies.push(Body {
+                victim: victim.name.clone(),
+                location: victim.location.clone(),
+                killed_at: death.killed_at,
+            });
+
+            Ok(victim)
+        }).await;
+
+        let victim = match result {
+            Ok(Ok(it)) => it,
+            Ok(Err(left)) => return Ok(nobody_killed(left)),
+            Err(error) => return Err(error.into())
+        };
+
+        let message = format!("You have been killed by {}", me.name);
+        let mut result = context.events.push(&victim.identity, EventKind::Killed, &message);
+
+        if result.is_ok() {
+            let message = format!("You are {} now", GHOST_ROLE);
+            result = context.events.push(&victim.identity, EventKind::RoleChanged, &message);
+        }
+
+        if let Err(error) = result {
+            return Err(error.into())
+        }
+
+        let message = models::KillResult {
+            killed_users_count: 1,
+            victim: Some(victim.name.clone()),
+            victim_tag: Some(victim.tag.clone()),
+            cooldown: whole_seconds(cooldown) as u32,
+        };
+
+        Ok(PostQueryResponse::HereIsTheKillResult(message))
+    }
+}
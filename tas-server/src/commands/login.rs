@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use log::{warn};
+use std::time::{Instant};
+
+use openapi_client::models;
+use openapi_client::{PostQueryResponse};
+
+use crate::audit::{self};
+use crate::commands::{Command, CommandResult, notify, whole_seconds};
+use crate::events::{EventKind};
+use crate::lockout::{LockedOut, Verdict};
+use crate::passwords::{verify_password_in_background};
+use crate::problems::{Failure};
+use crate::server::{SusContext, UserData};
+use crate::tasks::{self, Team};
+
+/// Logs the lockouts of a failed login
+/// and puts them into the audit log.
+fn report_lockouts(context: &SusContext, locked: LockedOut, member: Option<&str>, identity: &str) -> Result<(), Failure> {
+    let policy = *context.lockout.read()?.policy();
+
+    if locked.member {
+        warn!(target: "audit", "Locked out the member {} for {:?} after {} failed logins",
+            member.unwrap_or_default(), policy.lockout, policy.max_failures);
+
+        let member = member.map(str::to_owned);
+        audit::record_decision(context, "lockout", Some(identity.to_owned()), member, "LockedOut");
+    }
+
+    if locked.identity {
+        warn!(target: "audit", "Locked out the identity {} for {:?} after {} failed logins",
+            identity, policy.lockout, policy.max_failures);
+
+        audit::record_decision(context, "lockout", Some(identity.to_owned()), None, "LockedOut");
+    }
+
+    Ok(())
+}
+
+pub struct Login;
+
+#[async_trait]
+impl Command for Login {
+    fn name(&self) -> &str {
+        "login"
+    }
+
+    fn usage(&self) -> &str {
+        "login <name> <password>"
+    }
+
+    fn secret_arguments(&self) -> &[usize] {
+        &[2]
+    }
+
+    fn minimum_arity(&self) -> usize {
+        2
+    }
+
+    /// Or the admins couldn't get
+    /// in to start a new round.
+    fn runs_after_game_over(&self) -> bool {
+        true
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let name = &command[1];
+        let pass = &command[2];
+
+        // The lock is released before the slow check
+        let stored = {
+            let members_lock = match context.members.read() {
+                Ok(it) => it,
+                Err(error) => return Err(error.into())
+            };
+
+            if !members_lock.has_user(name) {
+                None
+            } else {
+                match members_lock.settings_for(name) {
+                    Ok(it) => Some(it.pass),
+                    Err(error) => return Err(error.into())
+                }
+            }
+        };
+
+        let member = stored.as_ref().map(|_| name.as_str());
+
+        // Counted as a failure until the password
+        // turns out to be right
+        let reserved = match context.lockout.write() {
+            Ok(mut it) => it.reserve(member, &me.identity),
+            Err(error) => return Err(error.into())
+        };
+
+        let locked = match reserved {
+            Ok(it) => it,
+            Err(Verdict::Locked(it)) => return Ok(PostQueryResponse::SomeRandomInformation(
+                notify(&format!("Too many failed logins, locked out for {} seconds", whole_seconds(it)))
+            )),
+            Err(it) => return Ok(PostQueryResponse::SomeRandomInformation(
+                notify(&format!("Too many failed logins, try again in {} seconds", whole_seconds(it.delay())))
+            )),
+        };
+
+        let stored = match stored {
+            Some(it) => it,
+            None => {
+                report_lockouts(context, locked, None, &me.identity)?;
+
+                return Ok(PostQueryResponse::SomeRandomInformation(
+                    notify(&format!("No such a user > {}", name))
+                ))
+            }
+        };
+
+        match verify_password_in_background(pass.clone(), stored).await {
+            Ok(true) => {}
+            Ok(false) => {
+                report_lockouts(context, locked, member, &me.identity)?;
+
+                return Ok(PostQueryResponse::SomeRandomInformation(
+                    notify("Incorrect password")
+                ))
+            }
+            Err(error) => return Err(error.into())
+        }
+
+        match context.lockout.write() {
+            Ok(mut it) => it.succeed(name, &me.identity),
+            Err(error) => return Err(error.into())
+        }
+
+        let role = {
+            let members_lock = match context.members.read() {
+                Ok(it) => it,
+                Err(error) => return Err(error.into())
+            };
+
+            let renamed = UserData {
+                name: name.clone(),
+                ..me.clone()
+            };
+
+            // Ghosts stay ghosts whoever they log in as
+            match members_lock.role_of(&renamed, &context.command_names) {
+                Ok(it) => it,
+                Err(error) => return Err(error.into())
+            }
+        };
+
+        let identity = me.identity.clone();
+        let new_name = name.clone();
+        let team = Team::of(&role);
+        let location = context.jail.to_virtual(&me.location);
+        let rules = context.tasks.clone();
+
+        let is_alive = context.game.call(move |state| {
+            state.sessions.rename(&identity, &new_name);
+
+            let renamed = match state.sessions.get(&identity) {
+                Some(it) => it.clone(),
+                None => return false,
+            };
+
+            if !tasks::plays(&renamed) {
+                return true
+            }
+
+            match team {
+                Some(Team::Crew) => tasks::assign(state, &renamed, &location, &rules, Instant::now()),
+                Some(Team::Impostors) | None => { state.tasks.remove(&identity); }
+            }
+
+            true
+        }).await;
+
+        match is_alive {
+            Ok(true) => {}
+            Ok(false) => return Err(Failure::unknown_identity()),
+            Err(error) => return Err(error.into())
+        }
+
+        let message = format!("You are {} now", role.title);
+
+        if let Err(error) = context.events.push(&me.identity, EventKind::RoleChanged, &message) {
+            return Err(error.into())
+        }
+
+        let message = models::Role {
+            title: role.title,
+            allowed_commands: role.allowed_commands,
+        };
+
+        Ok(PostQueryResponse::HereIsANewRoleForYou(message))
+    }
+}
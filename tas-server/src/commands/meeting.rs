@@ -0,0 +1,219 @@
+use std::time::{Instant};
+
+use async_trait::async_trait;
+
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, ambiguous_target, meeting_going_on, notify, whole_seconds};
+use crate::events::{EventKind};
+use crate::meetings::{self, Change, Meeting, Phase};
+use crate::problems::{Failure};
+use crate::server::{SusContext, UserData};
+
+/// Calls a meeting without a body, as
+/// many times as the rules allow.
+pub struct Emergency;
+
+#[async_trait]
+impl Command for Emergency {
+    fn name(&self) -> &str {
+        "emergency"
+    }
+
+    fn usage(&self) -> &str {
+        "emergency"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let my_identity = me.identity.clone();
+        let rules = context.meetings;
+
+        let result = context.game.call(move |state| {
+            let now = Instant::now();
+
+            let me = match state.sessions.get(&my_identity) {
+                Some(it) if it.is_ghost() => return Err(Failure::forbidden("ghost", "Ghosts can't call meetings")),
+                Some(it) => it.clone(),
+                None => return Err(Failure::unknown_identity()),
+            };
+
+            if state.current_meeting().is_some() {
+                return Err(meeting_going_on())
+            }
+
+            let called = state.emergencies.get(&me.name).cloned().unwrap_or(0);
+
+            if called >= rules.emergencies {
+                return Err(Failure::forbidden("no_emergencies", "You have no emergency meetings left"))
+            }
+
+            let last_ended_at = state.meeting.as_ref().and_then(|it| it.ended_at);
+
+            if let Some(it) = last_ended_at {
+                let left = (it + rules.emergency_cooldown).saturating_duration_since(now);
+
+                if left.as_millis() > 0 {
+                    let message = format!("The emergency button works again in {} seconds", whole_seconds(left));
+                    return Err(Failure::forbidden("emergency_cooldown", &message))
+                }
+            }
+
+            state.emergencies.insert(me.name.clone(), called + 1);
+            meetings::start(state, Meeting::new(&me, vec![], &rules, now));
+
+            Ok((me.location, rules.emergencies - called - 1))
+        }).await;
+
+        let (location, left) = match result {
+            Ok(Ok(it)) => it,
+            Ok(Err(failure)) => return Err(failure),
+            Err(error) => return Err(error.into())
+        };
+
+        let message = format!(
+            "{} has called an emergency meeting in {}, the discussion has started",
+            me.name,
+            context.jail.to_virtual(&location),
+        );
+
+        if let Err(error) = context.events.broadcast(EventKind::Meeting, &message) {
+            return Err(error.into())
+        }
+
+        Ok(PostQueryResponse::SomeRandomInformation(
+            notify(&format!("Called an emergency meeting, {} left", left))
+        ))
+    }
+}
+
+/// Records the vote of `me` for the session picked by the
+/// name and the tag, `None` being a skip. Ends the meeting
+/// once everyone alive has voted.
+async fn cast(
+    context: &SusContext,
+    me: &UserData,
+    target: Option<(String, Option<String>)>,
+) -> std::result::Result<(), Failure> {
+    let my_identity = me.identity.clone();
+
+    let result = context.game.call(move |state| {
+        match state.sessions.get(&my_identity) {
+            Some(it) if it.is_ghost() => return Err(Failure::forbidden("ghost", "Ghosts can't vote")),
+            Some(_) => {}
+            None => return Err(Failure::unknown_identity()),
+        }
+
+        match state.current_meeting() {
+            Some(it) if it.phase == Phase::Voting => {
+                if it.votes.contains_key(&my_identity) {
+                    return Err(Failure::bad_request("already_voted", "You have already voted"))
+                }
+            }
+            Some(_) => return Err(Failure::forbidden("not_voting", "The voting hasn't started yet")),
+            None => return Err(Failure::forbidden("not_voting", "There's nothing to vote on")),
+        }
+
+        let choice = match target {
+            Some((name, tag)) => {
+                let mut candidates = state.sessions.living_named(&name);
+                candidates.retain(|it| tag.as_ref().is_none_or(|that| &it.tag == that));
+
+                if candidates.len() > 1 {
+                    return Err(ambiguous_target(&name, &candidates))
+                }
+
+                match candidates.pop() {
+                    Some(it) => Some(it.identity),
+                    None => {
+                        let message = format!("Nobody alive goes by {}", name);
+                        return Err(Failure::bad_request("no_such_player", &message))
+                    }
+                }
+            }
+            None => None,
+        };
+
+        if let Some(it) = &mut state.meeting {
+            it.votes.insert(my_identity, choice);
+        }
+
+        if !meetings::has_everyone_voted(state) {
+            return Ok(None)
+        }
+
+        Ok(meetings::end(state, Instant::now()))
+    }).await;
+
+    let verdict = match result {
+        Ok(Ok(it)) => it,
+        Ok(Err(failure)) => return Err(failure),
+        Err(error) => return Err(error.into())
+    };
+
+    if let Some(it) = verdict {
+        if let Err(error) = meetings::announce(context, &Change::Ended(it)) {
+            return Err(error.into())
+        }
+    }
+
+    Ok(())
+}
+
+pub struct Vote;
+
+#[async_trait]
+impl Command for Vote {
+    fn name(&self) -> &str {
+        "vote"
+    }
+
+    fn usage(&self) -> &str {
+        "vote <name> [<tag>]"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let target = (command[1].clone(), command.get(2).cloned());
+
+        cast(context, &me, Some(target)).await?;
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify("Voted")))
+    }
+}
+
+pub struct Skip;
+
+#[async_trait]
+impl Command for Skip {
+    fn name(&self) -> &str {
+        "skip"
+    }
+
+    fn usage(&self) -> &str {
+        "skip"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        cast(context, &me, None).await?;
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify("Skipped")))
+    }
+}
@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use std::time::{Instant};
+
+use openapi_client::models;
+use openapi_client::{PostQueryResponse};
+
+use crate::acl::{Access, may_access, rules_for};
+use crate::commands::{Command, CommandResult, access_denied, meeting_going_on, notify};
+use crate::events::{EventKind, push_to_location};
+use crate::jail::{Lookup};
+use crate::meetings::{victims_at};
+use crate::problems::{Failure};
+use crate::server::{SusContext, UserData};
+use crate::tasks;
+
+pub struct Ls;
+
+#[async_trait]
+impl Command for Ls {
+    fn name(&self) -> &str {
+        "ls"
+    }
+
+    fn group(&self) -> &str {
+        "fs"
+    }
+
+    fn usage(&self) -> &str {
+        "ls"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let rules = match rules_for(context, &me) {
+            Ok(it) => it,
+            Err(error) => return Err(error.into())
+        };
+
+        let location = context.jail.to_virtual(&me.location);
+
+        if !rules.allows(&location, Access::Read) {
+            return Err(access_denied(&location))
+        }
+
+        let mut files = vec![];
+
+        let contents = match std::fs::read_dir(&me.location) {
+            Ok(it) => it,
+            Err(error) => return Err(error.into())
+        };
+
+        for it in contents {
+            let that = match it {
+                Ok(value) => value,
+                Err(error) => return Err(error.into())
+            };
+
+            let file = context.jail.to_virtual(&that.path());
+
+            // No need to show what can't be reached
+            if rules.allows(&file, Access::Read) {
+                files.push(file);
+            }
+        }
+
+        let identity = me.identity.clone();
+        let listed = location.clone();
+
+        if let Err(error) = context.game.call(move |state| tasks::list(state, &identity, &listed)).await {
+            return Err(error.into())
+        }
+
+        // Ghosts know where they lie anyway
+        let bodies = if me.is_ghost() {
+            None
+        } else {
+            let location = me.location.clone();
+
+            match context.game.call(move |state| victims_at(&state.bodies, &location)).await {
+                Ok(it) if it.is_empty() => None,
+                Ok(it) => Some(it),
+                Err(error) => return Err(error.into())
+            }
+        };
+
+        let message = models::FilesList {
+            files,
+            bodies,
+        };
+
+        return Ok(PostQueryResponse::HereAreTheFiles(message))
+    }
+}
+
+pub struct Cd;
+
+#[async_trait]
+impl Command for Cd {
+    fn name(&self) -> &str {
+        "cd"
+    }
+
+    fn group(&self) -> &str {
+        "fs"
+    }
+
+    fn usage(&self) -> &str {
+        "cd <directory>"
+    }
+
+    fn minimum_arity(&self) -> usize {
+        1
+    }
+
+    async fn handle(
+        &self,
+        command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let target = &command[1];
+        let current = me.location.clone();
+
+        let normalized = match context.jail.resolve(&current, target) {
+            Lookup::Found(it) => it,
+            Lookup::NotFound => {
+                return Ok(PostQueryResponse::SomeRandomInformation(
+                    notify("No such a path")
+                ))
+            }
+            Lookup::Escaped => {
+                return Ok(PostQueryResponse::SomeRandomInformation(
+                    notify("This path leads outside of the ship")
+                ))
+            }
+        };
+
+        if !normalized.is_dir() {
+            return Ok(PostQueryResponse::SomeRandomInformation(
+                notify("This is not a directory")
+            ))
+        }
+
+        match may_access(context, &me, &normalized, Access::Read) {
+            Ok(true) => {}
+            Ok(false) => return Err(access_denied(&context.jail.to_virtual(&normalized))),
+            Err(error) => return Err(error.into())
+        }
+
+        let identity = me.identity.clone();
+        let destination = normalized.clone();
+        let arrived_at = context.jail.to_virtual(&normalized);
+
+        let result = context.game.call(move |state| {
+            if !state.sessions.contains(&identity) {
+                return Err(Failure::unknown_identity())
+            }
+
+            if state.current_meeting().is_some() {
+                return Err(meeting_going_on())
+            }
+
+            state.sessions.relocate(&identity, &destination);
+            tasks::arrive(state, &identity, &arrived_at, Instant::now());
+            Ok(())
+        }).await;
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(failure)) => return Err(failure),
+            Err(error) => return Err(error.into())
+        }
+
+        if normalized != current {
+            let left = format!("{} left", me.name);
+            let entered = format!("{} entered", me.name);
+
+            let mut result = push_to_location(context, &current, &me.identity, EventKind::Left, &left).await;
+
+            if result.is_ok() {
+                result = push_to_location(context, &normalized, &me.identity, EventKind::Entered, &entered).await;
+            }
+
+            if let Err(error) = result {
+                return Err(error.into())
+            }
+        }
+
+        let message = models::MoveTo {
+            location: context.jail.to_virtual(&normalized),
+        };
+
+        Ok(PostQueryResponse::HereIsTheNewLocation(message))
+    }
+}
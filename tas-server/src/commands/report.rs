@@ -0,0 +1,84 @@
+use std::time::{Instant};
+
+use async_trait::async_trait;
+
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, meeting_going_on, notify};
+use crate::events::{EventKind};
+use crate::meetings::{self, Meeting, victims_at};
+use crate::problems::{Failure};
+use crate::server::{SusContext, UserData};
+
+/// Calls a meeting over the bodies found
+/// where the reporter stands.
+pub struct Report;
+
+#[async_trait]
+impl Command for Report {
+    fn name(&self) -> &str {
+        "report"
+    }
+
+    fn usage(&self) -> &str {
+        "report"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let my_identity = me.identity.clone();
+        let rules = context.meetings;
+
+        let result = context.game.call(move |state| {
+            let now = Instant::now();
+
+            let me = match state.sessions.get(&my_identity) {
+                Some(it) if it.is_ghost() => return Err(Failure::forbidden("ghost", "Ghosts can't report")),
+                Some(it) => it.clone(),
+                None => return Err(Failure::unknown_identity()),
+            };
+
+            if state.current_meeting().is_some() {
+                return Err(meeting_going_on())
+            }
+
+            let victims = victims_at(&state.bodies, &me.location);
+
+            if victims.is_empty() {
+                return Ok(None)
+            }
+
+            meetings::start(state, Meeting::new(&me, victims.clone(), &rules, now));
+
+            Ok(Some((me.location, victims)))
+        }).await;
+
+        let (location, victims) = match result {
+            Ok(Ok(Some(it))) => it,
+            Ok(Ok(None)) => {
+                return Ok(PostQueryResponse::SomeRandomInformation(
+                    notify("There's nothing to report here")
+                ))
+            }
+            Ok(Err(failure)) => return Err(failure),
+            Err(error) => return Err(error.into())
+        };
+
+        let message = format!(
+            "{} has found the body of {} in {}, the discussion has started",
+            me.name,
+            victims.join(", "),
+            context.jail.to_virtual(&location),
+        );
+
+        if let Err(error) = context.events.broadcast(EventKind::Meeting, &message) {
+            return Err(error.into())
+        }
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify("Reported")))
+    }
+}
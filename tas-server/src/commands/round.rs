@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, notify};
+use crate::events::{EventKind};
+use crate::server::{SusContext, UserData};
+
+/// Starts the game over for everyone on the ship: the
+/// ghosts are alive again, the bodies, the meetings and
+/// the tasks are gone, and the new tasks get drawn.
+pub struct NewRound;
+
+#[async_trait]
+impl Command for NewRound {
+    fn name(&self) -> &str {
+        "newround"
+    }
+
+    fn group(&self) -> &str {
+        "admin"
+    }
+
+    fn usage(&self) -> &str {
+        "newround"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        _me: UserData,
+    ) -> CommandResult {
+        let result = context.game.call(|state| {
+            state.game_over = None;
+            state.bodies.clear();
+            state.meeting = None;
+            state.emergencies.clear();
+            // Drawn again by `run_tasks()`
+            state.tasks.clear();
+            state.sessions.revive_all()
+        }).await;
+
+        let revived = match result {
+            Ok(it) => it,
+            Err(error) => return Err(error.into())
+        };
+
+        for it in &revived {
+            let role = match context.members.read() {
+                Ok(that) => match that.role_of(it, &context.command_names) {
+                    Ok(role) => role,
+                    Err(error) => return Err(error.into())
+                },
+                Err(error) => return Err(error.into())
+            };
+
+            let message = format!("You are {} now", role.title);
+
+            if let Err(error) = context.events.push(&it.identity, EventKind::RoleChanged, &message) {
+                return Err(error.into())
+            }
+        }
+
+        let message = "A new round has started, everyone is alive again";
+
+        if let Err(error) = context.events.broadcast(EventKind::NewRound, message) {
+            return Err(error.into())
+        }
+
+        log::info!("{}", message);
+
+        Ok(PostQueryResponse::SomeRandomInformation(
+            notify(&format!("Started a new round, {} revived", revived.len()))
+        ))
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+
+use openapi_client::{PostQueryResponse};
+
+use crate::commands::{Command, CommandResult, notify, whole_seconds};
+use crate::server::{SusContext, UserData};
+use crate::tasks::{self, Task, TaskKind, progress_bar};
+
+fn describe(task: &Task, now: Instant) -> String {
+    let mark = match task.done {
+        true => "x",
+        false => " ",
+    };
+
+    let left = match (task.spec.kind, task.done, task.since) {
+        (TaskKind::Stay, false, Some(it)) => {
+            let left = (it + Duration::from_secs(task.spec.seconds)).saturating_duration_since(now);
+            format!(" ({} left)", whole_seconds(left))
+        }
+        _ => String::new(),
+    };
+
+    format!("[{}] {}{}", mark, task.spec.describe(), left)
+}
+
+/// Shows the own tasks and how far
+/// the whole crew has got.
+pub struct Tasks;
+
+#[async_trait]
+impl Command for Tasks {
+    fn name(&self) -> &str {
+        "tasks"
+    }
+
+    fn usage(&self) -> &str {
+        "tasks"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let identity = me.identity.clone();
+
+        let result = context.game.call(move |state| {
+            let now = Instant::now();
+            let (done, total) = tasks::progress(&state.tasks);
+
+            let mine: Vec<String> = match state.tasks.get(&identity) {
+                Some(it) => it.iter().map(|that| describe(that, now)).collect(),
+                None => vec![],
+            };
+
+            (mine, done, total)
+        }).await;
+
+        let (mine, done, total) = match result {
+            Ok(it) => it,
+            Err(error) => return Err(error.into())
+        };
+
+        let mut lines = match mine.is_empty() {
+            true => vec!["You have no tasks".to_owned()],
+            false => mine,
+        };
+
+        lines.push(format!("Total > {}", progress_bar(done, total)));
+
+        Ok(PostQueryResponse::SomeRandomInformation(notify(&lines.join("\n"))))
+    }
+}
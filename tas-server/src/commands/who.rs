@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+
+use openapi_client::models;
+use openapi_client::{PostQueryResponse};
+
+use common::{Result};
+
+use crate::commands::{Command, CommandResult};
+use crate::server::{SusContext, UserData};
+
+/// Ghosts are only seen by the other ghosts.
+async fn collect_users(
+    context: &SusContext,
+    sees_ghosts: bool,
+) -> Result<Vec<models::UsersListUsers>> {
+    let jail = context.jail.clone();
+
+    let mut users = context.game.call(move |state| {
+        state.sessions.iter().filter(|it| sees_ghosts || !it.is_ghost()).map(|it| models::UsersListUsers {
+            user: it.name.clone(),
+            location: jail.to_virtual(&it.location),
+            tag: it.tag.clone(),
+        }).collect::<Vec<_>>()
+    }).await?;
+
+    users.sort_by(|a, b| {
+        a.user.cmp(&b.user)
+            .then_with(|| a.location.cmp(&b.location))
+            .then_with(|| a.tag.cmp(&b.tag))
+    });
+
+    Ok(users)
+}
+
+pub struct Who;
+
+#[async_trait]
+impl Command for Who {
+    fn name(&self) -> &str {
+        "who"
+    }
+
+    fn usage(&self) -> &str {
+        "who"
+    }
+
+    async fn handle(
+        &self,
+        _command: Vec<String>,
+        context: &SusContext,
+        me: UserData,
+    ) -> CommandResult {
+        let users = match collect_users(context, me.is_ghost()).await {
+            Ok(it) => it,
+            Err(error) => return Err(error.into())
+        };
+
+        let message = models::UsersList { users };
+
+        Ok(PostQueryResponse::HereAreYourCrewmates(message))
+    }
+}
@@ -0,0 +1,766 @@
+use clap::{App, Arg, ArgMatches};
+use serde::{Deserialize};
+
+use std::net::{SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration};
+
+use common::helpers::{misconfiguration};
+use common::parsing::{Codec, DEFAULT_MAX_FRAME_SIZE, MIN_FRAME_SIZE};
+use common::{Result};
+
+use crate::audit::{AuditOptions, AUDIT_FILE, DEFAULT_KEEP, DEFAULT_MAX_SIZE};
+use crate::jail::{Jail};
+use crate::lockout::{LockoutPolicy, DEFAULT_FREE_ATTEMPTS, DEFAULT_LOCKOUT, DEFAULT_MAX_FAILURES};
+use crate::meetings::{
+    MeetingRules,
+    DEFAULT_DISCUSSION,
+    DEFAULT_EMERGENCIES,
+    DEFAULT_EMERGENCY_COOLDOWN,
+    DEFAULT_VOTING,
+};
+use crate::sessions::{SessionLimits, DEFAULT_ABSOLUTE_TTL, DEFAULT_IDLE_TTL};
+use crate::server::{GameRules};
+use crate::snapshot::{SnapshotOptions, SNAPSHOT_FILE};
+use crate::tasks::{TaskKind, TaskRules, TaskSpec, DEFAULT_PER_CREWMATE};
+use crate::{DEFAULT_PORT, LEGACY_PORT};
+
+pub const DEFAULT_MEMBERS_FILE: &str = "members.json";
+pub const DEFAULT_TLS_KEY: &str = "tas-server/src/server-key.pem";
+pub const DEFAULT_TLS_CHAIN: &str = "tas-server/src/server-chain.pem";
+
+/// The longest of the durations, so that adding
+/// them to the current time can't overflow.
+pub const MAX_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
+
+/// Everything that can be set via the config file.
+/// The CLI flags (and the environment variables
+/// behind them) take precedence over the file.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub address: String,
+    /// Where the NetLab3 terminals connect.
+    pub legacy_address: String,
+    pub members: PathBuf,
+    /// The directory users can't leave.
+    pub root: PathBuf,
+    pub https: bool,
+    pub tls: TlsConfig,
+    pub legacy: LegacyConfig,
+    pub sessions: SessionsConfig,
+    pub login: LoginConfig,
+    pub audit: AuditConfig,
+    pub meetings: MeetingsConfig,
+    pub tasks: TasksConfig,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    pub key: PathBuf,
+    pub chain: PathBuf,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct LegacyConfig {
+    /// In bytes, both ways. Longer commands drop the
+    /// connection, longer responses are replaced
+    /// with a notification.
+    pub max_frame_size: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionsConfig {
+    /// In seconds.
+    pub idle_ttl: u64,
+    /// In seconds.
+    pub session_ttl: u64,
+    pub snapshot: PathBuf,
+    pub restore: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoginConfig {
+    /// Failed logins allowed before
+    /// the delays between them grow.
+    pub free_attempts: u32,
+    /// Failed logins in a row that lock
+    /// the member or the identity out.
+    pub max_failures: u32,
+    /// In seconds.
+    pub lockout: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    pub file: PathBuf,
+    /// In bytes.
+    pub max_size: u64,
+    /// How many rotated files to keep.
+    pub keep: u32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct MeetingsConfig {
+    /// In seconds.
+    pub discussion: u64,
+    /// In seconds.
+    pub voting: u64,
+    /// Emergency meetings each
+    /// member may call per game.
+    pub emergencies: u32,
+    /// In seconds, counted from
+    /// the end of the last meeting.
+    pub emergency_cooldown: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct TasksConfig {
+    /// How many tasks each crewmate
+    /// gets drawn from the `list`.
+    pub per_crewmate: u32,
+    pub list: Vec<TaskSpec>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            address: format!("127.0.0.1:{}", DEFAULT_PORT),
+            legacy_address: format!("127.0.0.1:{}", LEGACY_PORT),
+            members: PathBuf::from(DEFAULT_MEMBERS_FILE),
+            root: PathBuf::from("."),
+            https: false,
+            tls: TlsConfig::default(),
+            legacy: LegacyConfig::default(),
+            sessions: SessionsConfig::default(),
+            login: LoginConfig::default(),
+            audit: AuditConfig::default(),
+            meetings: MeetingsConfig::default(),
+            tasks: TasksConfig::default(),
+        }
+    }
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        TlsConfig {
+            key: PathBuf::from(DEFAULT_TLS_KEY),
+            chain: PathBuf::from(DEFAULT_TLS_CHAIN),
+        }
+    }
+}
+
+impl Default for LegacyConfig {
+    fn default() -> Self {
+        LegacyConfig {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE as u64,
+        }
+    }
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        SessionsConfig {
+            idle_ttl: DEFAULT_IDLE_TTL.as_secs(),
+            session_ttl: DEFAULT_ABSOLUTE_TTL.as_secs(),
+            snapshot: PathBuf::from(SNAPSHOT_FILE),
+            restore: true,
+        }
+    }
+}
+
+impl Default for LoginConfig {
+    fn default() -> Self {
+        LoginConfig {
+            free_attempts: DEFAULT_FREE_ATTEMPTS,
+            max_failures: DEFAULT_MAX_FAILURES,
+            lockout: DEFAULT_LOCKOUT.as_secs(),
+        }
+    }
+}
+
+impl Default for MeetingsConfig {
+    fn default() -> Self {
+        MeetingsConfig {
+            discussion: DEFAULT_DISCUSSION.as_secs(),
+            voting: DEFAULT_VOTING.as_secs(),
+            emergencies: DEFAULT_EMERGENCIES,
+            emergency_cooldown: DEFAULT_EMERGENCY_COOLDOWN.as_secs(),
+        }
+    }
+}
+
+impl Default for TasksConfig {
+    fn default() -> Self {
+        TasksConfig {
+            per_crewmate: DEFAULT_PER_CREWMATE,
+            list: vec![],
+        }
+    }
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        AuditConfig {
+            file: PathBuf::from(AUDIT_FILE),
+            max_size: DEFAULT_MAX_SIZE,
+            keep: DEFAULT_KEEP,
+        }
+    }
+}
+
+/// Adds the flags overriding the config
+/// values to the `app`.
+pub fn with_config_arguments<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app
+        .arg(Arg::with_name("config")
+            .long("config")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_CONFIG")
+            .help("The TOML file to read the settings from"))
+        .arg(Arg::with_name("address")
+            .long("address")
+            .takes_value(true)
+            .value_name("HOST:PORT")
+            .env("TAS_ADDRESS")
+            .help("Where to listen for the HTTP requests"))
+        .arg(Arg::with_name("legacy-address")
+            .long("legacy-address")
+            .takes_value(true)
+            .value_name("HOST:PORT")
+            .env("TAS_LEGACY_ADDRESS")
+            .help("Where to listen for the NetLab3 terminals"))
+        .arg(Arg::with_name("legacy-max-frame-size")
+            .long("legacy-max-frame-size")
+            .takes_value(true)
+            .value_name("BYTES")
+            .env("TAS_LEGACY_MAX_FRAME_SIZE")
+            .help("The longest message exchanged with a NetLab3 terminal"))
+        .arg(Arg::with_name("members")
+            .long("members")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_MEMBERS")
+            .help("The file with the roles and the users"))
+        .arg(Arg::with_name("https")
+            .long("https")
+            .help("Whether to use HTTPS or not"))
+        .arg(Arg::with_name("tls-key")
+            .long("tls-key")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_TLS_KEY")
+            .help("The private key for HTTPS"))
+        .arg(Arg::with_name("tls-chain")
+            .long("tls-chain")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_TLS_CHAIN")
+            .help("The certificate chain for HTTPS"))
+        .arg(Arg::with_name("root")
+            .long("root")
+            .takes_value(true)
+            .value_name("DIRECTORY")
+            .env("TAS_ROOT")
+            .help("The directory users can't leave, the current one by default"))
+        .arg(Arg::with_name("idle-ttl")
+            .long("idle-ttl")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_IDLE_TTL")
+            .help("How long a session may stay without running commands"))
+        .arg(Arg::with_name("session-ttl")
+            .long("session-ttl")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_SESSION_TTL")
+            .help("How long a session may last at most"))
+        .arg(Arg::with_name("snapshot")
+            .long("snapshot")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_SNAPSHOT")
+            .help("Where to save the sessions between restarts"))
+        .arg(Arg::with_name("no-restore")
+            .long("no-restore")
+            .help("Start with no sessions instead of restoring the saved ones"))
+        .arg(Arg::with_name("free-login-attempts")
+            .long("free-login-attempts")
+            .takes_value(true)
+            .value_name("COUNT")
+            .env("TAS_FREE_LOGIN_ATTEMPTS")
+            .help("How many failed logins are allowed before the delays grow"))
+        .arg(Arg::with_name("max-login-failures")
+            .long("max-login-failures")
+            .takes_value(true)
+            .value_name("COUNT")
+            .env("TAS_MAX_LOGIN_FAILURES")
+            .help("How many failed logins in a row lock the account out"))
+        .arg(Arg::with_name("lockout")
+            .long("lockout")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_LOCKOUT")
+            .help("How long a lockout lasts"))
+        .arg(Arg::with_name("audit-log")
+            .long("audit-log")
+            .takes_value(true)
+            .value_name("FILE")
+            .env("TAS_AUDIT_LOG")
+            .help("Where to log every API call"))
+        .arg(Arg::with_name("audit-max-size")
+            .long("audit-max-size")
+            .takes_value(true)
+            .value_name("BYTES")
+            .env("TAS_AUDIT_MAX_SIZE")
+            .help("The size to rotate the audit log at"))
+        .arg(Arg::with_name("audit-keep")
+            .long("audit-keep")
+            .takes_value(true)
+            .value_name("COUNT")
+            .env("TAS_AUDIT_KEEP")
+            .help("How many rotated audit logs to keep"))
+        .arg(Arg::with_name("discussion-time")
+            .long("discussion-time")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_DISCUSSION_TIME")
+            .help("How long a meeting is discussed before the voting"))
+        .arg(Arg::with_name("voting-time")
+            .long("voting-time")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_VOTING_TIME")
+            .help("How long the voting of a meeting lasts"))
+        .arg(Arg::with_name("emergencies")
+            .long("emergencies")
+            .takes_value(true)
+            .value_name("COUNT")
+            .env("TAS_EMERGENCIES")
+            .help("How many emergency meetings each member may call"))
+        .arg(Arg::with_name("emergency-cooldown")
+            .long("emergency-cooldown")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .env("TAS_EMERGENCY_COOLDOWN")
+            .help("How long after a meeting the next emergency one may be called"))
+        .arg(Arg::with_name("tasks")
+            .long("tasks")
+            .takes_value(true)
+            .value_name("COUNT")
+            .env("TAS_TASKS")
+            .help("How many tasks each crewmate gets"))
+}
+
+fn parse_seconds(value: &str, name: &str) -> Result<u64> {
+    match value.parse() {
+        Ok(it) => Ok(it),
+        Err(_) => misconfiguration(&format!("{} must be a number of seconds > {}", name, value)),
+    }
+}
+
+fn parse_size(value: &str, name: &str) -> Result<u64> {
+    match value.parse() {
+        Ok(it) => Ok(it),
+        Err(_) => misconfiguration(&format!("{} must be a number of bytes > {}", name, value)),
+    }
+}
+
+fn parse_count(value: &str, name: &str) -> Result<u32> {
+    match value.parse() {
+        Ok(it) => Ok(it),
+        Err(_) => misconfiguration(&format!("{} must be a number > {}", name, value)),
+    }
+}
+
+/// Relative paths in the file are taken
+/// relative to the file itself.
+fn relative_to(base: &Path, path: &mut PathBuf) {
+    if path.is_relative() {
+        *path = base.join(&*path);
+    }
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(it) => it,
+            Err(error) => return misconfiguration(&format!("Can't read {:?} > {}", path, error)),
+        };
+
+        let mut config: Config = match toml::from_str(&text) {
+            Ok(it) => it,
+            Err(error) => return misconfiguration(&format!("Can't parse {:?} > {}", path, error)),
+        };
+
+        let base = path.parent().unwrap_or(Path::new(""));
+
+        relative_to(base, &mut config.members);
+        relative_to(base, &mut config.root);
+        relative_to(base, &mut config.tls.key);
+        relative_to(base, &mut config.tls.chain);
+        relative_to(base, &mut config.sessions.snapshot);
+        relative_to(base, &mut config.audit.file);
+
+        Ok(config)
+    }
+
+    /// Reads the config file if there's one, applies
+    /// the overrides and checks the result.
+    pub fn from_matches(matches: &ArgMatches) -> Result<Config> {
+        let config = Config::read_matches(matches)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Like `from_matches()`, but leaves
+    /// the checks to the caller.
+    pub fn read_matches(matches: &ArgMatches) -> Result<Config> {
+        let mut config = match matches.value_of("config") {
+            Some(it) => Config::load(Path::new(it))?,
+            None => Config::default(),
+        };
+
+        if let Some(it) = matches.value_of("address") {
+            config.address = it.to_owned();
+        }
+
+        if let Some(it) = matches.value_of("legacy-address") {
+            config.legacy_address = it.to_owned();
+        }
+
+        if let Some(it) = matches.value_of("legacy-max-frame-size") {
+            config.legacy.max_frame_size = parse_size(it, "The legacy frame size")?;
+        }
+
+        if let Some(it) = matches.value_of("members") {
+            config.members = it.into();
+        }
+
+        if matches.is_present("https") {
+            config.https = true;
+        }
+
+        if let Some(it) = matches.value_of("tls-key") {
+            config.tls.key = it.into();
+        }
+
+        if let Some(it) = matches.value_of("tls-chain") {
+            config.tls.chain = it.into();
+        }
+
+        if let Some(it) = matches.value_of("root") {
+            config.root = it.into();
+        }
+
+        if let Some(it) = matches.value_of("idle-ttl") {
+            config.sessions.idle_ttl = parse_seconds(it, "The idle TTL")?;
+        }
+
+        if let Some(it) = matches.value_of("session-ttl") {
+            config.sessions.session_ttl = parse_seconds(it, "The session TTL")?;
+        }
+
+        if let Some(it) = matches.value_of("snapshot") {
+            config.sessions.snapshot = it.into();
+        }
+
+        if matches.is_present("no-restore") {
+            config.sessions.restore = false;
+        }
+
+        if let Some(it) = matches.value_of("free-login-attempts") {
+            config.login.free_attempts = parse_count(it, "The free login attempts")?;
+        }
+
+        if let Some(it) = matches.value_of("max-login-failures") {
+            config.login.max_failures = parse_count(it, "The max login failures")?;
+        }
+
+        if let Some(it) = matches.value_of("lockout") {
+            config.login.lockout = parse_seconds(it, "The lockout")?;
+        }
+
+        if let Some(it) = matches.value_of("audit-log") {
+            config.audit.file = it.into();
+        }
+
+        if let Some(it) = matches.value_of("audit-max-size") {
+            config.audit.max_size = parse_size(it, "The audit log size")?;
+        }
+
+        if let Some(it) = matches.value_of("audit-keep") {
+            config.audit.keep = parse_count(it, "The number of audit logs")?;
+        }
+
+        if let Some(it) = matches.value_of("discussion-time") {
+            config.meetings.discussion = parse_seconds(it, "The discussion time")?;
+        }
+
+        if let Some(it) = matches.value_of("voting-time") {
+            config.meetings.voting = parse_seconds(it, "The voting time")?;
+        }
+
+        if let Some(it) = matches.value_of("emergencies") {
+            config.meetings.emergencies = parse_count(it, "The number of emergency meetings")?;
+        }
+
+        if let Some(it) = matches.value_of("emergency-cooldown") {
+            config.meetings.emergency_cooldown = parse_seconds(it, "The emergency cooldown")?;
+        }
+
+        if let Some(it) = matches.value_of("tasks") {
+            config.tasks.per_crewmate = parse_count(it, "The number of tasks")?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self.problems().first() {
+            Some(it) => misconfiguration(it),
+            None => Ok(()),
+        }
+    }
+
+    /// Describes everything that makes no sense,
+    /// each prefixed with the key in question.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if let Err(message) = check_address(&self.address) {
+            problems.push(format!("address > {}", message));
+        }
+
+        if let Err(message) = check_address(&self.legacy_address) {
+            problems.push(format!("legacy_address > {}", message));
+        }
+
+        if self.legacy.max_frame_size < MIN_FRAME_SIZE as u64 {
+            problems.push(format!("legacy.max_frame_size > Must be at least {}", MIN_FRAME_SIZE));
+        }
+
+        if !self.members.is_file() {
+            problems.push(format!("members > The file doesn't exist > {:?}", self.members));
+        }
+
+        if !self.root.is_dir() {
+            problems.push(format!("root > Not a directory > {:?}", self.root));
+        }
+
+        if self.https {
+            if let Err(message) = check_readable(&self.tls.key) {
+                problems.push(format!("tls.key > {}", message));
+            }
+
+            if let Err(message) = check_readable(&self.tls.chain) {
+                problems.push(format!("tls.chain > {}", message));
+            }
+        }
+
+        if self.sessions.idle_ttl == 0 {
+            problems.push("sessions.idle_ttl > Must be positive".to_owned());
+        }
+
+        if self.sessions.session_ttl == 0 {
+            problems.push("sessions.session_ttl > Must be positive".to_owned());
+        }
+
+        let durations = [
+            ("sessions.idle_ttl", self.sessions.idle_ttl),
+            ("sessions.session_ttl", self.sessions.session_ttl),
+            ("login.lockout", self.login.lockout),
+            ("meetings.discussion", self.meetings.discussion),
+            ("meetings.voting", self.meetings.voting),
+            ("meetings.emergency_cooldown", self.meetings.emergency_cooldown),
+        ];
+
+        for (key, seconds) in durations {
+            if seconds > MAX_SECONDS {
+                problems.push(format!("{} > Must be at most {} seconds", key, MAX_SECONDS));
+            }
+        }
+
+        if self.login.max_failures == 0 {
+            problems.push("login.max_failures > Must be positive".to_owned());
+        }
+
+        if self.login.lockout == 0 {
+            problems.push("login.lockout > Must be positive".to_owned());
+        }
+
+        if self.audit.max_size == 0 {
+            problems.push("audit.max_size > Must be positive".to_owned());
+        }
+
+        if self.meetings.voting == 0 {
+            problems.push("meetings.voting > Must be positive".to_owned());
+        }
+
+        for it in &self.tasks.list {
+            if !it.path.starts_with('/') {
+                problems.push(format!("tasks.list > Must start at the root > {}", it.path));
+            } else if !self.root.join(it.path.trim_start_matches('/')).is_dir() {
+                problems.push(format!("tasks.list > Not a directory under the root > {}", it.path));
+            }
+
+            if it.kind == TaskKind::Stay && it.seconds == 0 {
+                problems.push(format!("tasks.list > The stay must be positive > {}", it.path));
+            }
+        }
+
+        problems
+    }
+
+    pub fn address(&self) -> Result<SocketAddr> {
+        parse_address(&self.address)
+    }
+
+    pub fn legacy_address(&self) -> Result<SocketAddr> {
+        parse_address(&self.legacy_address)
+    }
+
+    pub fn legacy_codec(&self) -> Codec {
+        Codec::new(self.legacy.max_frame_size as usize)
+    }
+
+    pub fn jail(&self) -> Result<Jail> {
+        Jail::new(&self.root)
+    }
+
+    pub fn limits(&self) -> SessionLimits {
+        SessionLimits {
+            idle: Duration::from_secs(self.sessions.idle_ttl),
+            absolute: Duration::from_secs(self.sessions.session_ttl),
+        }
+    }
+
+    pub fn lockout(&self) -> LockoutPolicy {
+        LockoutPolicy {
+            free_attempts: self.login.free_attempts,
+            max_failures: self.login.max_failures,
+            lockout: Duration::from_secs(self.login.lockout),
+        }
+    }
+
+    pub fn audit(&self) -> AuditOptions {
+        AuditOptions {
+            path: self.audit.file.clone(),
+            max_size: self.audit.max_size,
+            keep: self.audit.keep,
+        }
+    }
+
+    pub fn meetings(&self) -> MeetingRules {
+        MeetingRules {
+            discussion: Duration::from_secs(self.meetings.discussion),
+            voting: Duration::from_secs(self.meetings.voting),
+            emergencies: self.meetings.emergencies,
+            emergency_cooldown: Duration::from_secs(self.meetings.emergency_cooldown),
+        }
+    }
+
+    /// With the paths written
+    /// the way `cd` shows them.
+    pub fn tasks(&self) -> TaskRules {
+        let list = self.tasks.list.iter()
+            .map(|it| TaskSpec {
+                path: format!("/{}", it.path.trim_matches('/')),
+                ..it.clone()
+            })
+            .collect();
+
+        TaskRules {
+            per_crewmate: self.tasks.per_crewmate,
+            list,
+        }
+    }
+
+    pub fn rules(&self) -> GameRules {
+        GameRules {
+            limits: self.limits(),
+            lockout: self.lockout(),
+            meetings: self.meetings(),
+            tasks: self.tasks(),
+        }
+    }
+
+    pub fn snapshot(&self) -> SnapshotOptions {
+        SnapshotOptions {
+            path: self.sessions.snapshot.clone(),
+            restore: self.sessions.restore,
+        }
+    }
+}
+
+fn parse_address(address: &str) -> Result<SocketAddr> {
+    match check_address(address) {
+        Ok(it) => Ok(it),
+        Err(message) => misconfiguration(&message),
+    }
+}
+
+fn check_address(address: &str) -> std::result::Result<SocketAddr, String> {
+    match address.parse() {
+        Ok(it) => Ok(it),
+        Err(_) => Err(format!("Not a valid HOST:PORT address > {}", address)),
+    }
+}
+
+fn check_readable(path: &Path) -> std::result::Result<(), String> {
+    match std::fs::File::open(path) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!("Can't read {:?} > {}", path, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_problem(config: &Config, key: &str) -> bool {
+        config.problems().iter().any(|it| it.starts_with(key))
+    }
+
+    #[test]
+    fn bounds_the_session_ttls() {
+        let mut config = Config::default();
+
+        config.sessions.idle_ttl = MAX_SECONDS;
+        config.sessions.session_ttl = MAX_SECONDS;
+        assert!(!has_problem(&config, "sessions."));
+
+        config.sessions.idle_ttl = u64::MAX;
+        config.sessions.session_ttl = MAX_SECONDS + 1;
+        assert!(has_problem(&config, "sessions.idle_ttl"));
+        assert!(has_problem(&config, "sessions.session_ttl"));
+    }
+
+    #[test]
+    fn bounds_the_lockout() {
+        let mut config = Config::default();
+
+        config.login.lockout = MAX_SECONDS + 1;
+        assert!(has_problem(&config, "login.lockout"));
+    }
+
+    #[test]
+    fn bounds_the_meetings() {
+        let mut config = Config::default();
+
+        config.meetings.discussion = MAX_SECONDS + 1;
+        config.meetings.voting = MAX_SECONDS + 1;
+        config.meetings.emergency_cooldown = MAX_SECONDS + 1;
+        assert!(has_problem(&config, "meetings.discussion"));
+        assert!(has_problem(&config, "meetings.voting"));
+        assert!(has_problem(&config, "meetings.emergency_cooldown"));
+    }
+}
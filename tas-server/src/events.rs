@@ -0,0 +1,219 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path};
+use std::sync::{Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Notify};
+
+use openapi_client::models;
+
+use common::{Result};
+use common::shared::{IntoShared, Shared};
+
+use crate::server::{SusContext};
+
+/// How long `get_events` waits for something
+/// to happen before returning an empty list.
+pub const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// Older events are dropped if nobody
+/// picks them up.
+pub const MAX_PENDING_EVENTS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventKind {
+    Killed,
+    RoleChanged,
+    Entered,
+    Left,
+    Announcement,
+    Meeting,
+    GameOver,
+    NewRound,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Killed => "killed",
+            EventKind::RoleChanged => "role_changed",
+            EventKind::Entered => "entered",
+            EventKind::Left => "left",
+            EventKind::Announcement => "announcement",
+            EventKind::Meeting => "meeting",
+            EventKind::GameOver => "game_over",
+            EventKind::NewRound => "new_round",
+        }
+    }
+}
+
+struct Mailbox {
+    events: VecDeque<models::Event>,
+    notify: Arc<Notify>,
+}
+
+enum Drained {
+    Events(Vec<models::Event>),
+    Nothing(Arc<Notify>),
+    NoMailbox,
+}
+
+/// Per-identity queues of the things clients
+/// should learn about without asking.
+#[derive(Clone)]
+pub struct Events {
+    mailboxes: Shared<HashMap<String, Mailbox>>,
+    /// Set when the server stops, so that
+    /// nobody is kept waiting.
+    is_interrupted: Arc<AtomicBool>,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Events::new()
+    }
+}
+
+impl Events {
+    pub fn new() -> Self {
+        Events {
+            mailboxes: HashMap::new().to_shared(),
+            is_interrupted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn open(&self, identity: &str) -> Result<()> {
+        let mailbox = Mailbox {
+            events: VecDeque::new(),
+            notify: Arc::new(Notify::new()),
+        };
+
+        self.mailboxes.write()?.insert(identity.to_owned(), mailbox);
+        Ok(())
+    }
+
+    /// Forgets the mailbox along with
+    /// the undelivered events.
+    pub fn close(&self, identity: &str) -> Result<()> {
+        if let Some(it) = self.mailboxes.write()?.remove(identity) {
+            // Let the waiting ones see it's gone
+            it.notify.notify();
+        }
+
+        Ok(())
+    }
+
+    pub fn push(&self, identity: &str, kind: EventKind, message: &str) -> Result<()> {
+        let mut mailboxes = self.mailboxes.write()?;
+
+        if let Some(it) = mailboxes.get_mut(identity) {
+            deliver(it, kind, message);
+        }
+
+        Ok(())
+    }
+
+    pub fn broadcast(&self, kind: EventKind, message: &str) -> Result<()> {
+        let mut mailboxes = self.mailboxes.write()?;
+
+        for it in mailboxes.values_mut() {
+            deliver(it, kind, message);
+        }
+
+        Ok(())
+    }
+
+    /// Makes the current and the future `wait`s
+    /// return whatever there is right away.
+    pub fn interrupt(&self) -> Result<()> {
+        self.is_interrupted.store(true, Ordering::SeqCst);
+
+        for it in self.mailboxes.read()?.values() {
+            it.notify.notify();
+        }
+
+        Ok(())
+    }
+
+    fn drain(&self, identity: &str) -> Result<Drained> {
+        let mut mailboxes = self.mailboxes.write()?;
+
+        match mailboxes.get_mut(identity) {
+            Some(it) if it.events.is_empty() => Ok(Drained::Nothing(it.notify.clone())),
+            Some(it) => Ok(Drained::Events(it.events.drain(..).collect())),
+            None => Ok(Drained::NoMailbox),
+        }
+    }
+
+    /// Returns the pending events, or waits for new ones
+    /// for at most `timeout`. `None` means there's no
+    /// such a mailbox.
+    pub async fn wait(
+        &self,
+        identity: &str,
+        timeout: Duration,
+    ) -> Result<Option<Vec<models::Event>>> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let notify = match self.drain(identity)? {
+                Drained::Events(it) => return Ok(Some(it)),
+                Drained::Nothing(it) => it,
+                Drained::NoMailbox => return Ok(None),
+            };
+
+            let now = Instant::now();
+
+            if now >= deadline || self.is_interrupted.load(Ordering::SeqCst) {
+                return Ok(Some(vec![]))
+            }
+
+            // A notification may be left from the events
+            // drained earlier, so wake-ups are only hints
+            let _ = tokio::time::timeout(deadline - now, notify.notified()).await;
+        }
+    }
+}
+
+fn deliver(mailbox: &mut Mailbox, kind: EventKind, message: &str) {
+    if mailbox.events.len() >= MAX_PENDING_EVENTS {
+        mailbox.events.pop_front();
+    }
+
+    mailbox.events.push_back(models::Event {
+        kind: kind.as_str().to_owned(),
+        message: message.to_owned(),
+    });
+
+    mailbox.notify.notify();
+}
+
+/// Notifies everyone at the `location` except for
+/// the `author`. What ghosts do is only seen by
+/// the other ghosts.
+pub async fn push_to_location(
+    context: &SusContext,
+    location: &Path,
+    author: &str,
+    kind: EventKind,
+    message: &str,
+) -> Result<()> {
+    let key = location.to_owned();
+    let author_key = author.to_owned();
+
+    let identities = context.game.call(move |state| {
+        let is_ghost = |it: &str| state.sessions.get(it).is_some_and(|that| that.is_ghost());
+        let is_author_ghost = is_ghost(&author_key);
+
+        state.sessions.identities_at(&key).into_iter()
+            .filter(|it| *it != author_key && (!is_author_ghost || is_ghost(it)))
+            .collect::<Vec<_>>()
+    }).await?;
+
+    for it in identities {
+        context.events.push(&it, kind, message)?;
+    }
+
+    Ok(())
+}
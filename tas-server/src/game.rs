@@ -0,0 +1,147 @@
+use std::any::{Any};
+use std::collections::{HashMap};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Instant};
+
+use log::{error};
+use tokio::sync::{mpsc, oneshot};
+
+use common::{Result, ErrorKind};
+
+use crate::meetings::{Body, Meeting};
+use crate::sessions::{SessionStore};
+use crate::tasks::{Task, Team};
+
+/// Everything that changes while
+/// the game goes on.
+pub struct GameState {
+    pub sessions: SessionStore,
+    /// Identities of the evicted sessions
+    /// mapped to the time of eviction.
+    pub expired: HashMap<String, Instant>,
+    pub bodies: Vec<Body>,
+    /// The last one, see `current_meeting()`.
+    pub meeting: Option<Meeting>,
+    /// Member names mapped to the number of
+    /// the emergency meetings they have called.
+    pub emergencies: HashMap<String, u32>,
+    /// Identities mapped to their tasks, kept
+    /// for the ghosts to finish.
+    pub tasks: HashMap<String, Vec<Task>>,
+    /// The winners, once there are any.
+    pub game_over: Option<Team>,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::new()
+    }
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        GameState {
+            sessions: SessionStore::new(),
+            expired: HashMap::new(),
+            bodies: vec![],
+            meeting: None,
+            emergencies: HashMap::new(),
+            tasks: HashMap::new(),
+            game_over: None,
+        }
+    }
+
+    /// The meeting going on at the
+    /// moment, if there's any.
+    pub fn current_meeting(&self) -> Option<&Meeting> {
+        self.meeting.as_ref().filter(|it| it.is_going_on())
+    }
+}
+
+type Job = Box<dyn FnOnce(&mut GameState) + Send>;
+
+/// The handle to the task owning the `GameState`.
+/// Jobs are run one by one in the order they
+/// arrive, so there's no need for locks.
+#[derive(Clone)]
+pub struct Game {
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl Game {
+    /// Must be called from within
+    /// the tokio runtime.
+    pub fn spawn(state: GameState) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(state, receiver));
+        Game { jobs: sender }
+    }
+
+    /// Runs the `job` against the state and
+    /// returns whatever it returns. Jobs must
+    /// not call the `Game` themselves.
+    pub async fn call<F, R>(&self, job: F) -> Result<R>
+    where
+        F: FnOnce(&mut GameState) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        let wrapper: Job = Box::new(move |state| {
+            let _ = sender.send(job(state));
+        });
+
+        if self.jobs.send(wrapper).is_err() {
+            return game_is_over()
+        }
+
+        // Dropped without an answer if the job panics
+        match receiver.await {
+            Ok(it) => Ok(it),
+            Err(_) => ErrorKind::SendError {
+                message: "The game job has panicked".to_owned()
+            }.into(),
+        }
+    }
+}
+
+async fn run(mut state: GameState, mut jobs: mpsc::UnboundedReceiver<Job>) {
+    while let Some(job) = jobs.recv().await {
+        // One broken job mustn't stop the game for everyone
+        if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| job(&mut state))) {
+            error!("A game job has panicked > {}", describe(&*panic));
+        }
+    }
+}
+
+fn describe(panic: &(dyn Any + Send)) -> &str {
+    if let Some(it) = panic.downcast_ref::<&str>() {
+        it
+    } else if let Some(it) = panic.downcast_ref::<String>() {
+        it
+    } else {
+        "No message"
+    }
+}
+
+fn game_is_over<T>() -> Result<T> {
+    ErrorKind::SendError {
+        message: "The game task is not running".to_owned()
+    }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn survives_a_panicking_job() {
+        let game = Game::spawn(GameState::new());
+
+        let failed = game.call(|_| -> usize { panic!("Broken job") }).await;
+        assert!(failed.is_err());
+
+        let answer = game.call(|state| state.sessions.len()).await;
+        assert_eq!(answer.ok(), Some(0));
+    }
+}
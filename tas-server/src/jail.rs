@@ -0,0 +1,96 @@
+use std::path::{Component, Path, PathBuf};
+
+use common::helpers::{misconfiguration};
+use common::{Result};
+
+/// The outcome of resolving a user-provided path.
+pub enum Lookup {
+    Found(PathBuf),
+    NotFound,
+    Escaped,
+}
+
+/// A virtual root directory users can't leave.
+/// Inside the jail paths are shown as if the root
+/// was `/`, and `/` is always used as the separator.
+pub struct Jail {
+    root: PathBuf,
+}
+
+impl Jail {
+    pub fn new(root: &Path) -> Result<Jail> {
+        let root = root.canonicalize()?;
+
+        if !root.is_dir() {
+            return misconfiguration(&format!("The root is not a directory > {:?}", root))
+        }
+
+        Ok(Jail { root })
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    /// Resolves the `target` against the `current` location.
+    /// Absolute targets start at the jail root, and `..`
+    /// never climbs above it.
+    pub fn resolve(&self, current: &Path, target: &str) -> Lookup {
+        let mut location = if target.starts_with('/') || target.starts_with('\\') {
+            self.root.clone()
+        } else {
+            current.to_path_buf()
+        };
+
+        for part in target.split(['/', '\\']) {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    if location != self.root {
+                        location.pop();
+                    }
+                }
+                it => {
+                    // Things like `C:` are not plain names
+                    match Path::new(it).components().next() {
+                        Some(Component::Normal(_)) => location.push(it),
+                        _ => return Lookup::NotFound,
+                    }
+                }
+            }
+        }
+
+        // Follows symlinks, so the check
+        // below sees the real location
+        let normalized = match location.canonicalize() {
+            Ok(it) => it,
+            Err(_) => return Lookup::NotFound,
+        };
+
+        if !self.contains(&normalized) {
+            return Lookup::Escaped
+        }
+
+        Lookup::Found(normalized)
+    }
+
+    pub fn contains(&self, location: &Path) -> bool {
+        location.starts_with(&self.root)
+    }
+
+    /// Converts the real location into the
+    /// root-relative one.
+    pub fn to_virtual(&self, location: &Path) -> String {
+        let relative = match location.strip_prefix(&self.root) {
+            Ok(it) => it,
+            Err(_) => return "/".to_owned(),
+        };
+
+        let parts: Vec<String> = relative
+            .components()
+            .map(|it| it.as_os_str().to_string_lossy().into_owned())
+            .collect();
+
+        format!("/{}", parts.join("/"))
+    }
+}
@@ -0,0 +1,338 @@
+use std::net::{TcpListener as StdTcpListener};
+use std::sync::{Arc};
+use std::time::{Instant};
+
+use log::{info};
+use tokio::io::{ReadHalf, WriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::{JoinHandle};
+
+use openapi_client::{PostQueryResponse};
+
+use common::{Error, Result, ErrorKind, with_error_report};
+use common::parsing::{Codec};
+
+use crate::audit::{self, Call, outcome_of};
+use crate::commands::{Commands};
+use crate::events::{EventKind, LONG_POLL_TIMEOUT};
+use crate::messages::{ClientMessage, ServerMessage};
+use crate::server::{SusContext, UserData};
+use crate::sessions::{self, Activity};
+use crate::shutdown::{self, Shutdown};
+
+/// The running NetLab3 listener.
+pub struct LegacyListener {
+    /// Resolves once the acceptor and
+    /// all the connections are gone.
+    finished: JoinHandle<()>,
+}
+
+/// Serves the NetLab3 terminals: length-prefixed BSON
+/// messages over a persistent TCP connection. Each
+/// connection is a separate guest session. Must be
+/// called from within the tokio runtime.
+pub fn listen(
+    listener: StdTcpListener,
+    context: SusContext,
+    commands: Arc<Commands>,
+    codec: Codec,
+    shutdown: Shutdown,
+) -> Result<LegacyListener> {
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+
+    info!("Legacy terminals are served at {}", listener.local_addr()?);
+
+    let finished = tokio::spawn(accept(listener, context, commands, codec, shutdown));
+
+    Ok(LegacyListener {
+        finished,
+    })
+}
+
+impl LegacyListener {
+    /// Resolves once the `shutdown` has closed the
+    /// connections and the commands in progress
+    /// have finished.
+    pub async fn stopped(self) {
+        let _ = self.finished.await;
+    }
+}
+
+async fn accept(
+    mut listener: TcpListener,
+    context: SusContext,
+    commands: Arc<Commands>,
+    codec: Codec,
+    shutdown: Shutdown,
+) {
+    // Each connection holds a sender, so the
+    // channel closes once they're all gone
+    let (done, mut finished) = mpsc::channel::<()>(1);
+
+    loop {
+        let result = tokio::select! {
+            it = listener.accept() => it,
+            _ = shutdown.clone().wait() => break,
+        };
+
+        let stream = match result {
+            Ok((it, _)) => it,
+            Err(error) => {
+                println!("Error > {}", error);
+                continue
+            }
+        };
+
+        let context = context.clone();
+        let commands = commands.clone();
+        let shutdown = shutdown.clone();
+        let done = done.clone();
+
+        tokio::spawn(async move {
+            let result = serve_terminal(stream, &context, &commands, codec, shutdown).await;
+            with_error_report(|| result);
+            drop(done);
+        });
+    }
+
+    drop(done);
+    let _ = finished.recv().await;
+}
+
+/// Reads the commands, refusing the frames
+/// longer than the `codec` allows.
+struct Reader {
+    stream: ReadHalf<TcpStream>,
+    codec: Codec,
+}
+
+impl Reader {
+    async fn receive(&mut self) -> Result<ClientMessage> {
+        self.codec.read_message_async(&mut self.stream).await
+    }
+}
+
+/// Sends the messages of both the commands and
+/// the events, one whole frame at a time.
+struct Writer {
+    stream: Mutex<WriteHalf<TcpStream>>,
+    codec: Codec,
+}
+
+impl Writer {
+    /// A message longer than the `codec` allows is
+    /// replaced with a notification saying so, and
+    /// the connection stays open.
+    async fn send(&self, message: &ServerMessage) -> Result<()> {
+        let mut stream = self.stream.lock().await;
+
+        match self.codec.write_message_async(message, &mut *stream).await {
+            Err(Error { kind: ErrorKind::MessageSizeExceeded }) => {}
+            it => return it,
+        }
+
+        let apology = notification("The response is too long to send, try narrowing it down");
+
+        match self.codec.write_message_async(&apology, &mut *stream).await {
+            Err(Error { kind: ErrorKind::MessageSizeExceeded }) => Ok(()),
+            it => it,
+        }
+    }
+}
+
+async fn serve_terminal(
+    stream: TcpStream,
+    context: &SusContext,
+    commands: &Commands,
+    codec: Codec,
+    shutdown: Shutdown,
+) -> Result<()> {
+    let (reader, writer) = tokio::io::split(stream);
+
+    let mut reader = Reader {
+        stream: reader,
+        codec,
+    };
+
+    let writer = Writer {
+        stream: Mutex::new(writer),
+        codec,
+    };
+
+    let me = sessions::start_guest_session(context).await?;
+    let identity = me.identity.clone();
+
+    greet(&writer, context, &me).await?;
+
+    // Lets the notifier stop the commands
+    // once the session is gone
+    let (hang_up, hung_up) = shutdown::channel();
+
+    let serve = async {
+        let result = run_commands(&mut reader, &writer, context, commands, &identity, &shutdown, &hung_up).await;
+        sessions::end_session(context, &identity).await?;
+        result
+    };
+
+    let notify = async {
+        let result = push_events(&writer, context, &identity, &shutdown).await;
+        hang_up.trigger();
+        result
+    };
+
+    let (result, pushed) = futures::join!(serve, notify);
+
+    with_error_report(|| pushed);
+    result
+}
+
+async fn greet(writer: &Writer, context: &SusContext, me: &UserData) -> Result<()> {
+    let role = context.members.read()?.role_of(me, &context.command_names)?;
+
+    writer.send(&ServerMessage::Role {
+        title: role.title,
+        allowed_commands: role.allowed_commands,
+    }).await?;
+
+    writer.send(&ServerMessage::MoveTo {
+        location: context.jail.to_virtual(&me.location),
+    }).await
+}
+
+async fn run_commands(
+    reader: &mut Reader,
+    writer: &Writer,
+    context: &SusContext,
+    commands: &Commands,
+    identity: &str,
+    shutdown: &Shutdown,
+    hung_up: &Shutdown,
+) -> Result<()> {
+    loop {
+        let result = tokio::select! {
+            it = reader.receive() => it,
+            _ = shutdown.clone().wait() => return Ok(()),
+            _ = hung_up.clone().wait() => return Ok(()),
+        };
+
+        let message = match result {
+            Ok(it) => it,
+            Err(error) if is_disconnection(&error) => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        let command = match message {
+            ClientMessage::Execute { command } => command,
+        };
+
+        if command.is_empty() {
+            writer.send(&notification("Empty command")).await?;
+            continue
+        }
+
+        let me = match sessions::touch(context, identity, Instant::now()).await? {
+            Activity::Alive(it) => it,
+            Activity::Expired => {
+                writer.send(&notification("Your session has expired")).await?;
+                return Ok(())
+            }
+            // The notifier is the one
+            // to tell the user
+            Activity::Gone => return Ok(()),
+        };
+
+        let argv = commands.redact(command.clone());
+        let call = Call::start(context, None, "legacy", Some(identity.to_owned()), argv).await;
+
+        let response = commands.execute(command, context, me).await
+            .unwrap_or_else(PostQueryResponse::from);
+
+        audit::record(context, call, outcome_of(&response));
+        writer.send(&to_server_message(response)).await?;
+    }
+}
+
+/// Forwards the events as soon as they
+/// happen, since the connection allows that.
+async fn push_events(
+    writer: &Writer,
+    context: &SusContext,
+    identity: &str,
+    shutdown: &Shutdown,
+) -> Result<()> {
+    loop {
+        let events = match context.events.wait(identity, LONG_POLL_TIMEOUT).await? {
+            Some(it) => it,
+            None => break,
+        };
+
+        for it in events {
+            // The terminal gets the `Role` message anyway
+            if it.kind != EventKind::RoleChanged.as_str() {
+                writer.send(&notification(&it.message)).await?;
+            }
+        }
+
+        // The waits return at once after the shutdown,
+        // the session is ended along with the commands
+        if shutdown.is_triggered() {
+            return Ok(())
+        }
+    }
+
+    // The mailbox is only gone if the session is,
+    // so there's nothing left to serve
+    if sessions::has_expired(context, identity).await? {
+        let _ = writer.send(&notification("Your session has expired")).await;
+    }
+
+    Ok(())
+}
+
+fn notification(message: &str) -> ServerMessage {
+    ServerMessage::Notification {
+        message: message.to_owned()
+    }
+}
+
+fn to_server_message(response: PostQueryResponse) -> ServerMessage {
+    match response {
+        PostQueryResponse::HereIsTheKillResult(it) => ServerMessage::KillResult {
+            killed_users_count: it.killed_users_count,
+        },
+        PostQueryResponse::HereIsANewRoleForYou(it) => ServerMessage::Role {
+            title: it.title,
+            allowed_commands: it.allowed_commands,
+        },
+        PostQueryResponse::HereAreTheFiles(it) => ServerMessage::FilesList {
+            files: it.files,
+        },
+        PostQueryResponse::HereAreYourCrewmates(it) => ServerMessage::UsersList {
+            users: it.users.into_iter().map(|that| (that.user, that.location)).collect(),
+        },
+        PostQueryResponse::SomeRandomInformation(it) => notification(&it.message),
+        PostQueryResponse::HereIsTheNewLocation(it) => ServerMessage::MoveTo {
+            location: it.location,
+        },
+        PostQueryResponse::YourRequestIsJunk(it) => notification(&it.message),
+        PostQueryResponse::WhoAreYou(it) => notification(&it.message),
+        PostQueryResponse::YouMayNotDoThat(it) => notification(&it.message),
+        PostQueryResponse::YourSessionHasExpired => notification("Your session has expired"),
+        PostQueryResponse::SomethingWentWrong(it) => notification(&format!("Error > {}", it.message)),
+    }
+}
+
+fn is_disconnection(error: &common::Error) -> bool {
+    match &error.kind {
+        ErrorKind::NothingToRead => true,
+        ErrorKind::Io { source } => matches!(
+            source.kind(),
+            std::io::ErrorKind::UnexpectedEof
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+        ),
+        _ => false,
+    }
+}
@@ -1,27 +1,98 @@
 #![allow(missing_docs)]
 
-use clap::{App, Arg};
+use clap::{App, Arg, ArgMatches, SubCommand};
 
+use std::io::{BufRead};
+
+use commands::{Commands};
+use config::{Config, with_config_arguments};
+
+pub mod acl;
+pub mod audit;
+pub mod check;
+pub mod commands;
+pub mod config;
+pub mod events;
+pub mod game;
+pub mod jail;
+pub mod legacy;
+pub mod lockout;
+pub mod meetings;
 pub mod messages;
 pub mod members;
+pub mod passwords;
+pub mod problems;
+pub mod reload;
 pub mod server;
+pub mod sessions;
+pub mod shutdown;
+pub mod snapshot;
+pub mod tasks;
 
 pub const DEFAULT_PORT: u32 = 6969;
 
+/// Where the NetLab3 terminals connect.
+pub const LEGACY_PORT: u32 = 6970;
+
 /// Create custom server, wire it to the autogenerated router,
 /// and pass it to the web server.
 async fn generated_main() {
     env_logger::init();
 
-    let matches = App::new("server")
-        .arg(Arg::with_name("https")
-            .long("https")
-            .help("Whether to use HTTPS or not"))
+    let matches = with_config_arguments(App::new("server"))
+        .subcommand(SubCommand::with_name("hash-password")
+            .about("Prints the hash to put into the members file")
+            .arg(Arg::with_name("password")
+                .help("The password to hash, read from stdin if omitted")))
+        .subcommand(with_config_arguments(SubCommand::with_name("check-config")
+            .about("Reports every problem with the settings and the members file")))
         .get_matches();
 
-    let addr = format!("127.0.0.1:{}", DEFAULT_PORT);
+    if let Some(arguments) = matches.subcommand_matches("hash-password") {
+        return print_password_hash(arguments.value_of("password"));
+    }
+
+    if let Some(arguments) = matches.subcommand_matches("check-config") {
+        return report_problems(arguments);
+    }
+
+    let config = match Config::from_matches(&matches) {
+        Ok(it) => it,
+        Err(error) => {
+            println!("Error > {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    server::create(config, Commands::builtin()).await;
+}
+
+fn print_password_hash(password: Option<&str>) {
+    let password = match password {
+        Some(it) => it.to_owned(),
+        None => {
+            let mut line = String::new();
+            std::io::stdin().lock().read_line(&mut line).expect("Can't read the password");
+            line.trim_end_matches(&['\r', '\n'][..]).to_owned()
+        }
+    };
+
+    println!("{}", passwords::hash_password(&password).expect("Can't hash the password"));
+}
+
+fn report_problems(matches: &ArgMatches) {
+    let problems = check::find_problems(matches, &Commands::builtin());
+
+    if problems.is_empty() {
+        println!("The configuration is fine");
+        return
+    }
+
+    for it in &problems {
+        println!("Error > {}", it);
+    }
 
-    server::create(&addr, matches.is_present("https")).await;
+    std::process::exit(1);
 }
 
 pub fn start() {
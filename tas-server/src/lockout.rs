@@ -0,0 +1,403 @@
+use std::collections::{HashMap};
+use std::sync::{Arc};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_FREE_ATTEMPTS: u32 = 3;
+pub const DEFAULT_MAX_FAILURES: u32 = 10;
+pub const DEFAULT_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+/// The first delay after the free attempts,
+/// doubled with each next failure.
+pub const BACKOFF_BASE: Duration = Duration::from_secs(1);
+
+/// Lets the tests move the time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LockoutPolicy {
+    /// Failures allowed before the backoff kicks in.
+    pub free_attempts: u32,
+    /// Failures in a row that lock the login out.
+    pub max_failures: u32,
+    /// How long the lockout lasts. The failures are
+    /// also forgotten after this long without new ones.
+    pub lockout: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        LockoutPolicy {
+            free_attempts: DEFAULT_FREE_ATTEMPTS,
+            max_failures: DEFAULT_MAX_FAILURES,
+            lockout: DEFAULT_LOCKOUT,
+        }
+    }
+}
+
+/// What a failed login has locked out.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LockedOut {
+    pub member: bool,
+    pub identity: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Allowed,
+    /// Too many failures, the next
+    /// try must wait this long.
+    Wait(Duration),
+    Locked(Duration),
+}
+
+impl Verdict {
+    /// How long until the next try.
+    pub fn delay(&self) -> Duration {
+        match self {
+            Verdict::Allowed => Duration::ZERO,
+            Verdict::Wait(it) | Verdict::Locked(it) => *it,
+        }
+    }
+}
+
+struct Record {
+    failures: u32,
+    last_failure: Instant,
+    retry_at: Option<Instant>,
+    locked_until: Option<Instant>,
+}
+
+impl Record {
+    fn verdict(&self, now: Instant) -> Verdict {
+        if let Some(it) = self.locked_until {
+            if now < it {
+                return Verdict::Locked(it - now)
+            }
+        }
+
+        if let Some(it) = self.retry_at {
+            if now < it {
+                return Verdict::Wait(it - now)
+            }
+        }
+
+        Verdict::Allowed
+    }
+
+    /// Returns whether it locked the login out.
+    fn fail(&mut self, policy: &LockoutPolicy, now: Instant) -> bool {
+        if self.locked_until.is_some_and(|it| it <= now) {
+            self.failures = 0;
+            self.locked_until = None;
+        }
+
+        self.failures += 1;
+        self.last_failure = now;
+
+        if self.failures >= policy.max_failures {
+            self.locked_until = Some(now + policy.lockout);
+            self.retry_at = None;
+            return true
+        }
+
+        if self.failures >= policy.free_attempts {
+            let exponent = (self.failures - policy.free_attempts).min(16);
+            let delay = (BACKOFF_BASE * 2u32.pow(exponent)).min(policy.lockout);
+            self.retry_at = Some(now + delay);
+        }
+
+        false
+    }
+}
+
+/// Counts the failed logins per member and per identity,
+/// so that guessing either a password or many passwords
+/// from one session gets slower and then stops.
+pub struct Lockout {
+    policy: LockoutPolicy,
+    clock: Arc<dyn Clock>,
+    members: HashMap<String, Record>,
+    identities: HashMap<String, Record>,
+}
+
+fn record<'a>(records: &'a mut HashMap<String, Record>, key: &str, now: Instant) -> &'a mut Record {
+    records.entry(key.to_owned()).or_insert_with(|| Record {
+        failures: 0,
+        last_failure: now,
+        retry_at: None,
+        locked_until: None,
+    })
+}
+
+fn stricter(one: Verdict, other: Verdict) -> Verdict {
+    match (one, other) {
+        (Verdict::Locked(a), Verdict::Locked(b)) => Verdict::Locked(a.max(b)),
+        (Verdict::Locked(it), _) | (_, Verdict::Locked(it)) => Verdict::Locked(it),
+        (Verdict::Wait(a), Verdict::Wait(b)) => Verdict::Wait(a.max(b)),
+        (Verdict::Wait(it), _) | (_, Verdict::Wait(it)) => Verdict::Wait(it),
+        _ => Verdict::Allowed,
+    }
+}
+
+impl Lockout {
+    pub fn new(policy: LockoutPolicy, clock: Arc<dyn Clock>) -> Self {
+        Lockout {
+            policy,
+            clock,
+            members: HashMap::new(),
+            identities: HashMap::new(),
+        }
+    }
+
+    /// Whether the `identity` may try
+    /// to log in as the `member` now.
+    pub fn check(&self, member: &str, identity: &str) -> Verdict {
+        let now = self.clock.now();
+
+        let by_member = self.members.get(member).map_or(Verdict::Allowed, |it| it.verdict(now));
+        let by_identity = self.identities.get(identity).map_or(Verdict::Allowed, |it| it.verdict(now));
+
+        stricter(by_member, by_identity)
+    }
+
+    /// Checks the attempt and counts it as a failure in one go,
+    /// so that concurrent logins can't all pass the check before
+    /// the first one fails. `succeed()` takes it back if the
+    /// password turns out to be right.
+    pub fn reserve(&mut self, member: Option<&str>, identity: &str) -> Result<LockedOut, Verdict> {
+        match self.check(member.unwrap_or_default(), identity) {
+            Verdict::Allowed => Ok(self.fail(member, identity)),
+            it => Err(it),
+        }
+    }
+
+    /// `member` is `None` when there's no such a member,
+    /// so that made up names don't pile up here.
+    pub fn fail(&mut self, member: Option<&str>, identity: &str) -> LockedOut {
+        let now = self.clock.now();
+        let mut locked = LockedOut::default();
+
+        self.forget_old(now);
+
+        if let Some(name) = member {
+            locked.member = record(&mut self.members, name, now).fail(&self.policy, now);
+        }
+
+        locked.identity = record(&mut self.identities, identity, now).fail(&self.policy, now);
+        locked
+    }
+
+    pub fn succeed(&mut self, member: &str, identity: &str) {
+        self.members.remove(member);
+        self.identities.remove(identity);
+    }
+
+    /// Returns whether the `member`
+    /// had any failures to forget.
+    pub fn unlock(&mut self, member: &str) -> bool {
+        self.members.remove(member).is_some()
+    }
+
+    pub fn policy(&self) -> &LockoutPolicy {
+        &self.policy
+    }
+
+    fn forget_old(&mut self, now: Instant) {
+        let lockout = self.policy.lockout;
+
+        let is_recent = |it: &Record| {
+            now.saturating_duration_since(it.last_failure) < lockout
+                || it.locked_until.is_some_and(|until| now < until)
+        };
+
+        self.members.retain(|_, it| is_recent(it));
+        self.identities.retain(|_, it| is_recent(it));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Mutex, RwLock};
+
+    struct FakeClock {
+        now: Mutex<Instant>,
+    }
+
+    impl FakeClock {
+        fn new() -> Arc<Self> {
+            Arc::new(FakeClock {
+                now: Mutex::new(Instant::now()),
+            })
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.now.lock().unwrap() += duration;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            *self.now.lock().unwrap()
+        }
+    }
+
+    fn policy() -> LockoutPolicy {
+        LockoutPolicy {
+            free_attempts: 2,
+            max_failures: 5,
+            lockout: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn allows_the_free_attempts() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        assert_eq!(lockout.check("sam", "a"), Verdict::Allowed);
+    }
+
+    #[test]
+    fn doubles_the_delay() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        lockout.fail(Some("sam"), "a");
+        assert_eq!(lockout.check("sam", "a"), Verdict::Wait(Duration::from_secs(1)));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(lockout.check("sam", "a"), Verdict::Allowed);
+
+        lockout.fail(Some("sam"), "a");
+        assert_eq!(lockout.check("sam", "a"), Verdict::Wait(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn tracks_members_and_identities_apart() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        lockout.fail(Some("sam"), "b");
+
+        // Someone guessing the password of sam
+        assert_ne!(lockout.check("sam", "c"), Verdict::Allowed);
+        assert_eq!(lockout.check("john", "a"), Verdict::Allowed);
+
+        // Someone guessing the names
+        lockout.fail(None, "d");
+        lockout.fail(None, "d");
+        assert_ne!(lockout.check("john", "d"), Verdict::Allowed);
+    }
+
+    #[test]
+    fn locks_out_and_lets_back_in() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        for _ in 0..5 {
+            clock.advance(Duration::from_secs(10));
+            lockout.fail(Some("sam"), "a");
+        }
+
+        assert_eq!(lockout.check("sam", "b"), Verdict::Locked(Duration::from_secs(60)));
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(lockout.check("sam", "b"), Verdict::Locked(Duration::from_secs(1)));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(lockout.check("sam", "b"), Verdict::Allowed);
+
+        // Starts over after the lockout
+        lockout.fail(Some("sam"), "b");
+        assert_eq!(lockout.check("sam", "c"), Verdict::Allowed);
+    }
+
+    #[test]
+    fn unlocks_on_demand() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        for _ in 0..5 {
+            lockout.fail(Some("sam"), "a");
+        }
+
+        assert!(lockout.unlock("sam"));
+        assert_eq!(lockout.check("sam", "b"), Verdict::Allowed);
+        assert!(!lockout.unlock("sam"));
+    }
+
+    #[test]
+    fn forgets_old_failures() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        lockout.fail(Some("sam"), "a");
+
+        clock.advance(Duration::from_secs(60));
+        lockout.fail(Some("john"), "b");
+
+        lockout.fail(Some("sam"), "a");
+        assert_eq!(lockout.check("sam", "a"), Verdict::Allowed);
+    }
+
+    #[test]
+    fn succeeding_resets_the_count() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        lockout.succeed("sam", "a");
+        lockout.fail(Some("sam"), "a");
+
+        assert_eq!(lockout.check("sam", "a"), Verdict::Allowed);
+    }
+
+    #[test]
+    fn concurrent_logins_reserve_their_attempts() {
+        let clock = FakeClock::new();
+        let lockout = Arc::new(RwLock::new(Lockout::new(policy(), clock.clone())));
+
+        let threads: Vec<_> = (0..20)
+            .map(|it| {
+                let lockout = lockout.clone();
+                let identity = it.to_string();
+                std::thread::spawn(move || lockout.write().unwrap().reserve(Some("sam"), &identity).is_ok())
+            })
+            .collect();
+
+        let verified = threads.into_iter()
+            .map(|it| it.join().unwrap())
+            .filter(|it| *it)
+            .count();
+
+        assert!(verified <= policy().free_attempts as usize);
+    }
+
+    #[test]
+    fn succeeding_takes_the_reservation_back() {
+        let clock = FakeClock::new();
+        let mut lockout = Lockout::new(policy(), clock.clone());
+
+        lockout.fail(Some("sam"), "a");
+        assert!(lockout.reserve(Some("sam"), "a").is_ok());
+        lockout.succeed("sam", "a");
+
+        assert_eq!(lockout.check("sam", "a"), Verdict::Allowed);
+    }
+}
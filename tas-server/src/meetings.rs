@@ -0,0 +1,296 @@
+use std::collections::{HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use openapi_client::models;
+
+use common::{Result, with_error_report};
+
+use crate::events::{EventKind};
+use crate::game::{GameState};
+use crate::jail::{Jail};
+use crate::members::{GHOST_ROLE};
+use crate::server::{Death, SusContext, UserData};
+use crate::shutdown::{Shutdown};
+use crate::tasks;
+
+pub const DEFAULT_DISCUSSION: Duration = Duration::from_secs(60);
+pub const DEFAULT_VOTING: Duration = Duration::from_secs(30);
+pub const DEFAULT_EMERGENCIES: u32 = 1;
+pub const DEFAULT_EMERGENCY_COOLDOWN: Duration = Duration::from_secs(120);
+
+/// How often the phases are moved on.
+pub const MEETING_TICK: Duration = Duration::from_secs(1);
+
+/// Who the ejected are killed by.
+pub const EJECTED_BY: &str = "the crew";
+
+#[derive(Clone, Copy, Debug)]
+pub struct MeetingRules {
+    pub discussion: Duration,
+    pub voting: Duration,
+    /// Emergency meetings each
+    /// member may call per game.
+    pub emergencies: u32,
+    /// Counted from the end of the last meeting.
+    pub emergency_cooldown: Duration,
+}
+
+impl Default for MeetingRules {
+    fn default() -> Self {
+        MeetingRules {
+            discussion: DEFAULT_DISCUSSION,
+            voting: DEFAULT_VOTING,
+            emergencies: DEFAULT_EMERGENCIES,
+            emergency_cooldown: DEFAULT_EMERGENCY_COOLDOWN,
+        }
+    }
+}
+
+/// Left where a session was killed,
+/// until the next meeting starts.
+#[derive(Clone, Debug)]
+pub struct Body {
+    pub victim: String,
+    pub location: PathBuf,
+    pub killed_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Discussion,
+    Voting,
+    Over,
+}
+
+impl Phase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Discussion => "discussion",
+            Phase::Voting => "voting",
+            Phase::Over => "over",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Meeting {
+    /// The name of whoever has called it.
+    pub called_by: String,
+    pub location: PathBuf,
+    /// Whose bodies have been reported,
+    /// none for an emergency.
+    pub victims: Vec<String>,
+    pub phase: Phase,
+    pub voting_at: Instant,
+    pub ends_at: Instant,
+    /// Set once it's over, even if early.
+    pub ended_at: Option<Instant>,
+    /// The voters mapped to whom they have voted for, both
+    /// identities, so that the namesakes count apart.
+    /// `None` is a skip.
+    pub votes: HashMap<String, Option<String>>,
+}
+
+impl Meeting {
+    pub fn new(caller: &UserData, victims: Vec<String>, rules: &MeetingRules, now: Instant) -> Self {
+        let voting_at = now + rules.discussion;
+
+        Meeting {
+            called_by: caller.name.clone(),
+            location: caller.location.clone(),
+            victims,
+            phase: Phase::Discussion,
+            voting_at,
+            ends_at: voting_at + rules.voting,
+            ended_at: None,
+            votes: HashMap::new(),
+        }
+    }
+
+    pub fn is_going_on(&self) -> bool {
+        self.phase != Phase::Over
+    }
+
+    /// Until the end of the current phase.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        match self.phase {
+            Phase::Discussion => self.voting_at.saturating_duration_since(now),
+            Phase::Voting => self.ends_at.saturating_duration_since(now),
+            Phase::Over => Duration::from_secs(0),
+        }
+    }
+
+    /// The identity voted for the most, unless
+    /// it's a tie or the skips are as many.
+    pub fn winner(&self) -> Option<(String, u32)> {
+        let mut counts: HashMap<&String, u32> = HashMap::new();
+        let mut skips = 0;
+
+        for it in self.votes.values() {
+            match it {
+                Some(that) => *counts.entry(that).or_insert(0) += 1,
+                None => skips += 1,
+            }
+        }
+
+        let most = counts.values().cloned().max().unwrap_or(0);
+        let mut leaders = counts.into_iter().filter(|(_, count)| *count == most);
+
+        match (leaders.next(), leaders.next()) {
+            (Some((identity, count)), None) if count > skips => Some((identity.clone(), count)),
+            _ => None,
+        }
+    }
+}
+
+/// How a meeting has ended.
+pub struct Verdict {
+    pub ejected: Option<(UserData, u32)>,
+    pub votes: usize,
+}
+
+/// What a tick has changed.
+pub enum Change {
+    VotingStarted,
+    Ended(Verdict),
+}
+
+/// What the `identity` is shown of the `meeting`.
+pub fn describe(meeting: &Meeting, identity: &str, jail: &Jail, now: Instant) -> models::Meeting {
+    models::Meeting {
+        phase: meeting.phase.as_str().to_owned(),
+        called_by: meeting.called_by.clone(),
+        location: jail.to_virtual(&meeting.location),
+        victims: meeting.victims.clone(),
+        remaining: meeting.remaining(now).as_secs(),
+        votes: meeting.votes.len() as u32,
+        has_voted: meeting.votes.contains_key(identity),
+    }
+}
+
+/// Whose bodies lie at the `location`.
+pub fn victims_at(bodies: &[Body], location: &Path) -> Vec<String> {
+    bodies.iter()
+        .filter(|it| it.location == location)
+        .map(|it| it.victim.clone())
+        .collect()
+}
+
+/// Starts the meeting and clears the bodies, the
+/// caller checks that no meeting is going on.
+pub fn start(state: &mut GameState, meeting: Meeting) {
+    // The crew cleans up while meeting
+    state.bodies.clear();
+    state.meeting = Some(meeting);
+}
+
+/// Counts the votes and ejects the winner.
+pub fn end(state: &mut GameState, now: Instant) -> Option<Verdict> {
+    let meeting = match &mut state.meeting {
+        Some(it) if it.is_going_on() => it,
+        _ => return None,
+    };
+
+    meeting.phase = Phase::Over;
+    meeting.ended_at = Some(now);
+
+    let votes = meeting.votes.len();
+    let winner = meeting.winner();
+
+    let death = Death {
+        killer: EJECTED_BY.to_owned(),
+        killed_at: Utc::now(),
+    };
+
+    let ejected = winner.and_then(|(identity, count)| {
+        state.sessions.kill(&identity, &death).map(|it| (it, count))
+    });
+
+    Some(Verdict {
+        ejected,
+        votes,
+    })
+}
+
+/// Moves the meeting on to the next
+/// phase once the time has come.
+pub fn advance(state: &mut GameState, now: Instant) -> Option<Change> {
+    let meeting = match &mut state.meeting {
+        Some(it) => it,
+        None => return None,
+    };
+
+    match meeting.phase {
+        Phase::Discussion if now >= meeting.voting_at => {
+            meeting.phase = Phase::Voting;
+            Some(Change::VotingStarted)
+        }
+        Phase::Voting if now >= meeting.ends_at => end(state, now).map(Change::Ended),
+        _ => None,
+    }
+}
+
+/// Whether everyone alive has voted, so
+/// that there's no need to wait.
+pub fn has_everyone_voted(state: &GameState) -> bool {
+    let meeting = match &state.meeting {
+        Some(it) => it,
+        None => return false,
+    };
+
+    // Those who haven't logged in can't vote
+    state.sessions.iter()
+        .filter(|it| tasks::plays(it))
+        .all(|it| meeting.votes.contains_key(&it.identity))
+}
+
+/// Tells everyone what has changed.
+pub fn announce(context: &SusContext, change: &Change) -> Result<()> {
+    let message = match change {
+        Change::VotingStarted => {
+            "The voting has started, vote <name> [<tag>] or skip".to_owned()
+        }
+        Change::Ended(Verdict { ejected: Some((user, count)), votes }) => {
+            let message = format!("You have been ejected with {} votes", count);
+            context.events.push(&user.identity, EventKind::Killed, &message)?;
+
+            let message = format!("You are {} now", GHOST_ROLE);
+            context.events.push(&user.identity, EventKind::RoleChanged, &message)?;
+
+            format!("{} ({}) has been ejected with {} of {} votes", user.name, user.tag, count, votes)
+        }
+        Change::Ended(Verdict { ejected: None, votes }) => {
+            format!("Nobody has been ejected, {} have voted", votes)
+        }
+    };
+
+    context.events.broadcast(EventKind::Meeting, &message)
+}
+
+/// Moves the meetings on in time.
+pub async fn run_meetings(context: SusContext, shutdown: Shutdown) {
+    let mut interval = tokio::time::interval(MEETING_TICK);
+
+    let stopped = shutdown.wait();
+    tokio::pin!(stopped);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = &mut stopped => return,
+        }
+
+        let now = Instant::now();
+
+        let result = match context.game.call(move |state| advance(state, now)).await {
+            Ok(Some(it)) => announce(&context, &it),
+            Ok(None) => Ok(()),
+            Err(error) => Err(error),
+        };
+
+        with_error_report(|| result);
+    }
+}
@@ -1,18 +1,41 @@
 use std::fs::{File};
+use std::path::{Path};
 use std::collections::{HashMap};
+use std::time::{Duration};
+
+use log::{info};
 
 use common::helpers::{misconfiguration};
 use common::serializable;
 use common::{Result};
 
+use crate::acl::{PathRules};
+use crate::passwords::{hash_password, is_hashed, verify_password};
+use crate::server::{UserData};
+
+pub mod roles;
+
+pub use roles::{ExtendedRole, RoleSettings};
+
+use roles::{bare_name, matches, parse_entry, resolve};
+
+/// The role of the killed sessions,
+/// whoever they were logged in as.
+pub const GHOST_ROLE: &str = "ghost";
+
+/// The group of the commands running the ship,
+/// whoever may use them is on neither team.
+pub const ADMIN_GROUP: &str = "admin";
+
 serializable! {
     pub struct UserSettings {
         pub role: String,
+        /// A PHC string, see `passwords::hash_password()`.
         pub pass: String,
     }
 
     pub struct Members {
-        roles: HashMap<String, Vec<String>>,
+        roles: HashMap<String, RoleSettings>,
         users: HashMap<String, UserSettings>,
     }
 }
@@ -20,18 +43,41 @@ serializable! {
 #[derive(Clone, Debug)]
 pub struct Role {
     pub title: String,
+    /// Resolved, with the inherited ones
+    /// and without the group prefixes.
     pub allowed_commands: Vec<String>,
+    pub paths: PathRules,
+    /// How long the sessions with the
+    /// role must wait between kills.
+    pub kill_cooldown: Duration,
+    /// Allows some of the `ADMIN_GROUP` commands.
+    pub is_admin: bool,
 }
 
 impl Members {
-    pub fn role(&self, role: &str) -> Result<Role> {
-        if !self.roles.contains_key(role) {
-            return misconfiguration(&format!("No such a role > {}", role))
-        }
+    /// `known_commands` are qualified with their
+    /// group, see `Commands::qualified_names()`.
+    pub fn role(&self, role: &str, known_commands: &[String]) -> Result<Role> {
+        let resolved = match resolve(&self.roles, role, known_commands, &mut vec![]) {
+            Ok(it) => it,
+            Err(message) => return misconfiguration(&message),
+        };
+
+        let allowed_commands = known_commands.iter()
+            .filter(|it| resolved.commands.contains(*it))
+            .map(|it| bare_name(it).to_owned())
+            .collect();
+
+        let is_admin = known_commands.iter()
+            .filter(|it| resolved.commands.contains(*it))
+            .any(|it| it.strip_prefix(ADMIN_GROUP).is_some_and(|rest| rest.starts_with('.')));
 
         let it = Role {
             title: role.to_owned(),
-            allowed_commands: self.roles[role].clone(),
+            allowed_commands,
+            paths: resolved.paths,
+            kill_cooldown: Duration::from_secs(resolved.kill_cooldown.unwrap_or(0)),
+            is_admin,
         };
 
         Ok(it)
@@ -51,22 +97,261 @@ impl Members {
         Ok(settings)
     }
 
-    pub fn role_for(&self, user: &str) -> Result<Role> {
+    pub fn role_for(&self, user: &str, known_commands: &[String]) -> Result<Role> {
         let settings = self.settings_for(user)?;
-        self.role(&settings.role)
+        self.role(&settings.role, known_commands)
+    }
+
+    /// The role the session acts with, which
+    /// is the ghost one once it's killed.
+    pub fn role_of(&self, user: &UserData, known_commands: &[String]) -> Result<Role> {
+        if user.is_ghost() {
+            return self.role(GHOST_ROLE, known_commands)
+        }
+
+        self.role_for(&user.name, known_commands)
+    }
+
+    /// Describes the first problem making
+    /// the members unusable, see `problems()`.
+    pub fn check(&self, known_commands: &[String]) -> std::result::Result<(), String> {
+        match self.problems(known_commands).into_iter().next() {
+            Some(it) => Err(it),
+            None => Ok(()),
+        }
+    }
+
+    /// Lists everything making the members unusable: no
+    /// `guest` to start the sessions with, no role for the
+    /// ghosts, users with unknown roles and roles allowing
+    /// commands that are not in `known_commands`. Each is
+    /// prefixed with the key.
+    pub fn problems(&self, known_commands: &[String]) -> Vec<String> {
+        let mut problems = vec![];
+
+        if !self.has_user("guest") {
+            problems.push("users.guest > There must be a guest user, new sessions start as it".to_owned());
+        }
+
+        if !self.roles.contains_key(GHOST_ROLE) {
+            problems.push(format!("roles.{} > There must be a role for the killed users", GHOST_ROLE));
+        }
+
+        for name in sorted_keys(&self.users) {
+            let role = &self.users[name].role;
+
+            if !self.roles.contains_key(role) {
+                problems.push(format!("users.{}.role > No such a role > {}", name, role));
+            }
+        }
+
+        let mut is_missing_parents = false;
+
+        for title in sorted_keys(&self.roles) {
+            let settings = &self.roles[title];
+
+            for it in settings.parents() {
+                if !self.roles.contains_key(it) {
+                    problems.push(format!("roles.{}.inherits > No such a role > {}", title, it));
+                    is_missing_parents = true;
+                }
+            }
+
+            for it in settings.entries() {
+                let (pattern, _) = parse_entry(it);
+
+                if !known_commands.iter().any(|that| matches(pattern, that)) {
+                    problems.push(format!("roles.{} > No such a command > {}", title, it));
+                }
+            }
+        }
+
+        // Only loops are left to find
+        if !is_missing_parents {
+            for title in sorted_keys(&self.roles) {
+                if let Err(message) = resolve(&self.roles, title, known_commands, &mut vec![]) {
+                    problems.push(message);
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Lists the members with empty passwords and the ones
+    /// sharing a password. Salted hashes only match when
+    /// copied over, so only those duplicates are found.
+    pub fn password_problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let mut seen: HashMap<&str, &str> = HashMap::new();
+
+        for name in sorted_keys(&self.users) {
+            let pass = &self.users[name].pass;
+
+            let is_empty = if is_hashed(pass) {
+                verify_password("", pass)
+            } else {
+                pass.is_empty()
+            };
+
+            // Anyone may become a guest anyway
+            if is_empty && name != "guest" {
+                problems.push(format!("users.{}.pass > The password is empty", name));
+            }
+
+            match seen.get(pass.as_str()) {
+                Some(other) => problems.push(format!("users.{}.pass > The same password as {}", name, other)),
+                None => {
+                    seen.insert(pass, name);
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// `pass` is expected to be hashed already.
+    pub fn add_user(&mut self, name: &str, pass: String, role: &str) -> std::result::Result<(), String> {
+        if self.has_user(name) {
+            return Err(format!("The user already exists > {}", name))
+        }
+
+        if !self.roles.contains_key(role) {
+            return Err(format!("No such a role > {}", role))
+        }
+
+        let settings = UserSettings {
+            role: role.to_owned(),
+            pass,
+        };
+
+        self.users.insert(name.to_owned(), settings);
+        Ok(())
+    }
+
+    pub fn remove_user(&mut self, name: &str) -> std::result::Result<(), String> {
+        match self.users.remove(name) {
+            Some(_) => Ok(()),
+            None => Err(format!("No such a user > {}", name)),
+        }
+    }
+
+    /// `pass` is expected to be hashed already.
+    pub fn set_password(&mut self, name: &str, pass: String) -> std::result::Result<(), String> {
+        let settings = match self.users.get_mut(name) {
+            Some(it) => it,
+            None => return Err(format!("No such a user > {}", name)),
+        };
+
+        settings.pass = pass;
+        Ok(())
+    }
+
+    pub fn add_role(&mut self, title: &str, commands: Vec<String>) -> std::result::Result<(), String> {
+        if self.roles.contains_key(title) {
+            return Err(format!("The role already exists > {}", title))
+        }
+
+        self.roles.insert(title.to_owned(), RoleSettings::Flat(commands));
+        Ok(())
+    }
+
+    pub fn remove_role(&mut self, title: &str) -> std::result::Result<(), String> {
+        match self.roles.remove(title) {
+            Some(_) => Ok(()),
+            None => Err(format!("No such a role > {}", title)),
+        }
+    }
+
+    /// Adds an entry, so patterns and
+    /// denials work here as well.
+    pub fn grant(&mut self, title: &str, command: &str) -> std::result::Result<(), String> {
+        let commands = match self.roles.get_mut(title) {
+            Some(it) => it.entries_mut(),
+            None => return Err(format!("No such a role > {}", title)),
+        };
+
+        if commands.iter().any(|it| it == command) {
+            return Err(format!("The role {} already allows {}", title, command))
+        }
+
+        commands.push(command.to_owned());
+        Ok(())
+    }
+
+    /// Removes the entry, which may not be enough
+    /// if a parent allows the same command.
+    pub fn revoke(&mut self, title: &str, command: &str) -> std::result::Result<(), String> {
+        let commands = match self.roles.get_mut(title) {
+            Some(it) => it.entries_mut(),
+            None => return Err(format!("No such a role > {}", title)),
+        };
+
+        let count = commands.len();
+        commands.retain(|it| it != command);
+
+        if commands.len() == count {
+            return Err(format!("The role {} doesn't allow {}", title, command))
+        }
+
+        Ok(())
+    }
+
+    /// Replaces plaintext passwords with their hashes
+    /// and returns the number of the replaced ones.
+    pub fn hash_plaintext_passwords(&mut self) -> Result<usize> {
+        let mut count = 0;
+
+        for settings in self.users.values_mut() {
+            if !is_hashed(&settings.pass) {
+                settings.pass = hash_password(&settings.pass)?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
     }
 }
 
-const MEMBERS_FILE: &'static str = "members.json";
+/// Parses the file as is, unlike
+/// `load_members()`.
+pub fn read_members(path: &Path) -> Result<Members> {
+    let mut file = File::open(path)?;
+    let it = serde_json::from_reader(&mut file)?;
+    Ok(it)
+}
+
+/// Hashes the plaintext passwords
+/// and saves them if there are any.
+pub fn load_members(path: &Path) -> Result<Members> {
+    let mut it = read_members(path)?;
+
+    let hashed = it.hash_plaintext_passwords()?;
+
+    if hashed > 0 {
+        info!("Hashed {} plaintext passwords in {:?}", hashed, path);
+        save_members(&it, path)?;
+    }
 
-pub fn load_members() -> Result<Members> {
-    let mut file = File::open(MEMBERS_FILE)?;
-    let it: Members = serde_json::from_reader(&mut file)?;
     Ok(it)
 }
 
-pub fn save_members(members: &Members) -> Result<()> {
-    let mut file = File::create(MEMBERS_FILE)?;
+fn sorted_keys<T>(map: &HashMap<String, T>) -> Vec<&String> {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+    keys
+}
+
+/// The file is replaced at once, so a crash in
+/// the middle leaves the previous version intact.
+pub fn save_members(members: &Members, path: &Path) -> Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    let mut file = File::create(&temporary)?;
     serde_json::to_writer_pretty(&mut file, members)?;
+    file.sync_all()?;
+
+    std::fs::rename(&temporary, path)?;
     Ok(())
 }
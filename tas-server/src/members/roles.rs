@@ -0,0 +1,177 @@
+use std::collections::{HashMap, HashSet};
+
+use common::serializable;
+
+use crate::acl::{PathRules};
+
+serializable! {
+    /// Either the original flat list of commands, or one
+    /// with the parents to inherit the commands of. The
+    /// entries are applied in order on top of whatever the
+    /// parents allow. An entry is a command name or a pattern
+    /// where `*` matches anything, like `fs.*`, and a leading
+    /// `!` denies the matching commands.
+    #[serde(untagged)]
+    pub enum RoleSettings {
+        Flat(Vec<String>),
+        Extended(ExtendedRole),
+    }
+
+    #[serde(deny_unknown_fields)]
+    pub struct ExtendedRole {
+        #[serde(default)]
+        pub inherits: Vec<String>,
+        #[serde(default)]
+        pub commands: Vec<String>,
+        #[serde(default, skip_serializing_if = "PathRules::is_empty")]
+        pub paths: PathRules,
+        /// Seconds to wait between
+        /// two kills, see `Role`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub kill_cooldown: Option<u64>,
+    }
+}
+
+/// What a role ends up with once
+/// the parents are taken into account.
+pub struct Resolved {
+    /// Qualified names.
+    pub commands: HashSet<String>,
+    pub paths: PathRules,
+    /// Seconds, its own or the longest inherited one.
+    pub kill_cooldown: Option<u64>,
+}
+
+impl RoleSettings {
+    pub fn parents(&self) -> &[String] {
+        match self {
+            RoleSettings::Flat(_) => &[],
+            RoleSettings::Extended(it) => &it.inherits,
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        match self {
+            RoleSettings::Flat(it) => it,
+            RoleSettings::Extended(it) => &it.commands,
+        }
+    }
+
+    pub fn paths(&self) -> Option<&PathRules> {
+        match self {
+            RoleSettings::Flat(_) => None,
+            RoleSettings::Extended(it) => Some(&it.paths),
+        }
+    }
+
+    pub fn kill_cooldown(&self) -> Option<u64> {
+        match self {
+            RoleSettings::Flat(_) => None,
+            RoleSettings::Extended(it) => it.kill_cooldown,
+        }
+    }
+
+    pub fn entries_mut(&mut self) -> &mut Vec<String> {
+        match self {
+            RoleSettings::Flat(it) => it,
+            RoleSettings::Extended(it) => &mut it.commands,
+        }
+    }
+}
+
+/// Splits `!pattern` into the pattern
+/// and whether it's a denial.
+pub fn parse_entry(entry: &str) -> (&str, bool) {
+    match entry.strip_prefix('!') {
+        Some(it) => (it, true),
+        None => (entry, false),
+    }
+}
+
+/// `known` commands are qualified with their group, like
+/// `fs.ls`, and patterns may match either the qualified
+/// name or the bare one.
+pub fn matches(pattern: &str, known: &str) -> bool {
+    let bare = bare_name(known);
+    glob(pattern.as_bytes(), known.as_bytes()) || glob(pattern.as_bytes(), bare.as_bytes())
+}
+
+/// `fs.ls` becomes `ls`.
+pub fn bare_name(qualified: &str) -> &str {
+    match qualified.rfind('.') {
+        Some(index) => &qualified[index + 1..],
+        None => qualified,
+    }
+}
+
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|it| glob(rest, &text[it..])),
+        Some((first, rest)) => match text.split_first() {
+            Some((that, others)) => first == that && glob(rest, others),
+            None => false,
+        },
+    }
+}
+
+/// Finds the `known` commands the role allows and the
+/// path rules, following the parents. `path` holds the
+/// roles being resolved, so that inheriting from oneself
+/// is reported, not looped.
+pub fn resolve(
+    roles: &HashMap<String, RoleSettings>,
+    title: &str,
+    known: &[String],
+    path: &mut Vec<String>,
+) -> std::result::Result<Resolved, String> {
+    if path.iter().any(|it| it == title) {
+        return Err(format!("roles.{} > Inherits from itself via {}", title, path.join(" -> ")))
+    }
+
+    let settings = match roles.get(title) {
+        Some(it) => it,
+        None => return Err(format!("No such a role > {}", title)),
+    };
+
+    let mut allowed = HashSet::new();
+    let mut paths = PathRules::default();
+    let mut kill_cooldown = None;
+
+    path.push(title.to_owned());
+
+    for parent in settings.parents() {
+        let inherited = resolve(roles, parent, known, path)?;
+        allowed.extend(inherited.commands);
+        paths.extend(&inherited.paths, false);
+        kill_cooldown = kill_cooldown.max(inherited.kill_cooldown);
+    }
+
+    path.pop();
+
+    if let Some(it) = settings.paths() {
+        paths.extend(it, true);
+    }
+
+    if let Some(it) = settings.kill_cooldown() {
+        kill_cooldown = Some(it);
+    }
+
+    for entry in settings.entries() {
+        let (pattern, is_denial) = parse_entry(entry);
+
+        for it in known.iter().filter(|it| matches(pattern, it)) {
+            if is_denial {
+                allowed.remove(it);
+            } else {
+                allowed.insert(it.clone());
+            }
+        }
+    }
+
+    Ok(Resolved {
+        commands: allowed,
+        paths,
+        kill_cooldown,
+    })
+}
@@ -0,0 +1,57 @@
+use argon2::{Argon2};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use rand_core::{OsRng};
+
+use common::{ErrorKind, Result};
+
+/// Produces a salted PHC string like `$argon2id$v=19$...`.
+pub fn hash_password(pass: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    match Argon2::default().hash_password(pass.as_bytes(), &salt) {
+        Ok(it) => Ok(it.to_string()),
+        Err(error) => ErrorKind::PasswordHashing {
+            message: format!("{}", error),
+        }.into(),
+    }
+}
+
+/// Like `hash_password()`, but on the
+/// blocking pool, see `verify_password_in_background()`.
+pub async fn hash_password_in_background(pass: String) -> Result<String> {
+    match tokio::task::spawn_blocking(move || hash_password(&pass)).await {
+        Ok(it) => it,
+        Err(error) => ErrorKind::PasswordHashing {
+            message: format!("{}", error),
+        }.into(),
+    }
+}
+
+/// Anything that doesn't parse as a PHC
+/// string is treated as a plaintext password.
+pub fn is_hashed(stored: &str) -> bool {
+    PasswordHash::new(stored).is_ok()
+}
+
+/// The comparison of the hashes runs
+/// in constant time.
+pub fn verify_password(pass: &str, stored: &str) -> bool {
+    let hash = match PasswordHash::new(stored) {
+        Ok(it) => it,
+        Err(_) => return false,
+    };
+
+    Argon2::default().verify_password(pass.as_bytes(), &hash).is_ok()
+}
+
+/// Like `verify_password()`, but on the blocking pool,
+/// since Argon2 is slow on purpose and would hold up
+/// the other requests.
+pub async fn verify_password_in_background(pass: String, stored: String) -> Result<bool> {
+    match tokio::task::spawn_blocking(move || verify_password(&pass, &stored)).await {
+        Ok(it) => Ok(it),
+        Err(error) => ErrorKind::PasswordHashing {
+            message: format!("{}", error),
+        }.into(),
+    }
+}
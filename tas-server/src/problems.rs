@@ -0,0 +1,156 @@
+use log::{error};
+
+use openapi_client::models;
+use openapi_client::{
+    GetEventsResponse,
+    GetMeetingResponse,
+    GetMyselfResponse,
+    GetNewUserResponse,
+    PostQueryResponse,
+};
+
+use common::{Error, ErrorKind};
+
+/// The HTTP statuses the failures map to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// 400, the arguments make no sense.
+    BadRequest,
+    /// 401, no identity or an unknown one.
+    Unauthorized,
+    /// 403, the role doesn't allow it.
+    Forbidden,
+    /// 500, a fault of the server.
+    Internal,
+}
+
+/// Why a call failed. Becomes the matching
+/// `models::Problem` response of the operation.
+#[derive(Debug)]
+pub struct Failure {
+    pub status: Status,
+    pub problem: models::Problem,
+}
+
+impl Failure {
+    pub fn new(status: Status, code: &str, message: &str) -> Self {
+        Failure {
+            status,
+            problem: models::Problem {
+                code: code.to_owned(),
+                message: message.to_owned(),
+            },
+        }
+    }
+
+    pub fn bad_request(code: &str, message: &str) -> Self {
+        Failure::new(Status::BadRequest, code, message)
+    }
+
+    pub fn unauthorized(code: &str, message: &str) -> Self {
+        Failure::new(Status::Unauthorized, code, message)
+    }
+
+    pub fn forbidden(code: &str, message: &str) -> Self {
+        Failure::new(Status::Forbidden, code, message)
+    }
+
+    /// The session has ended or
+    /// has never existed.
+    pub fn unknown_identity() -> Self {
+        Failure::unauthorized("unknown_identity", "No such a session")
+    }
+}
+
+/// The status and the `code` of the
+/// problem for the errors of each kind.
+pub fn classify(kind: &ErrorKind) -> (Status, &'static str) {
+    match kind {
+        ErrorKind::NothingToRead => (Status::BadRequest, "nothing_to_read"),
+        ErrorKind::MessageSizeExceeded => (Status::BadRequest, "message_too_large"),
+        ErrorKind::MalformedMessage { .. } => (Status::BadRequest, "malformed_message"),
+        ErrorKind::ParsingJson { .. } => (Status::BadRequest, "malformed_json"),
+        ErrorKind::DeserializingBson { .. } => (Status::BadRequest, "malformed_bson"),
+        ErrorKind::ConversionBson { .. } => (Status::BadRequest, "malformed_bson"),
+        ErrorKind::Io { .. } => (Status::Internal, "io"),
+        ErrorKind::SerializingBson { .. } => (Status::Internal, "serialization"),
+        ErrorKind::PoisonedLock { .. } => (Status::Internal, "poisoned_lock"),
+        ErrorKind::SendError { .. } => (Status::Internal, "channel"),
+        ErrorKind::SystemTime { .. } => (Status::Internal, "system_time"),
+        ErrorKind::Configuration { .. } => (Status::Internal, "configuration"),
+        ErrorKind::Uuid { .. } => (Status::Internal, "uuid"),
+        ErrorKind::PasswordHashing { .. } => (Status::Internal, "password_hashing"),
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        let (status, code) = classify(&error.kind);
+
+        if status != Status::Internal {
+            return Failure::new(status, code, &format!("{}", error.kind))
+        }
+
+        // The details stay in the logs
+        error!("Failed to serve a call > {}", error);
+        Failure::new(status, code, "Something went wrong on the server side")
+    }
+}
+
+impl From<std::io::Error> for Failure {
+    fn from(error: std::io::Error) -> Self {
+        Error::from(error).into()
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for Failure {
+    fn from(error: std::sync::PoisonError<T>) -> Self {
+        Error::from(error).into()
+    }
+}
+
+impl From<serde_json::Error> for Failure {
+    fn from(error: serde_json::Error) -> Self {
+        Error::from(error).into()
+    }
+}
+
+/// Picks the response variant for each status, the ones
+/// the operation can't run into end up as the 500.
+macro_rules! failure_responses {
+    ($($response:ident { $($status:ident => $variant:ident),* })*) => {
+        $(
+            impl From<Failure> for $response {
+                fn from(failure: Failure) -> Self {
+                    match failure.status {
+                        $(Status::$status => $response::$variant(failure.problem),)*
+                        _ => $response::SomethingWentWrong(failure.problem),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+failure_responses! {
+    GetEventsResponse {
+        BadRequest => YourRequestIsJunk,
+        Unauthorized => WhoAreYou
+    }
+    GetMeetingResponse {
+        BadRequest => YourRequestIsJunk,
+        Unauthorized => WhoAreYou
+    }
+    GetMyselfResponse {
+        BadRequest => YourRequestIsJunk,
+        Unauthorized => WhoAreYou
+    }
+    GetNewUserResponse {
+        BadRequest => YourRequestIsJunk
+    }
+    PostQueryResponse {
+        BadRequest => YourRequestIsJunk,
+        Unauthorized => WhoAreYou,
+        Forbidden => YouMayNotDoThat
+    }
+}
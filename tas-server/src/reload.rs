@@ -0,0 +1,137 @@
+use std::path::{Path};
+use std::time::{Duration, SystemTime};
+
+use log::{info, warn};
+
+use common::helpers::{misconfiguration};
+use common::{Result};
+
+use crate::members::{read_members, Members};
+use crate::server::{SusContext};
+use crate::shutdown::{Shutdown};
+
+/// How often the members file is checked for changes.
+pub const MEMBERS_POLL_PERIOD: Duration = Duration::from_secs(2);
+
+/// Re-reads the members file and swaps it in if it's
+/// usable. The sessions pick up the new roles on their
+/// next command. Returns `false` if the file is the one
+/// the admins have saved, since it's in memory already.
+pub async fn reload_members(context: &SusContext) -> Result<bool> {
+    let path = match &context.members_file {
+        Some(it) => it.clone(),
+        None => return Ok(false),
+    };
+
+    // Ordered with the admin changes,
+    // so that none of them gets lost
+    let _changing = context.members_changing.lock().await;
+
+    let modified = modified_at(&path);
+
+    if modified.is_some() && modified == *context.members_saved_at.read()? {
+        return Ok(false)
+    }
+
+    // Hashing takes a while, so the role
+    // lookups go on in the meantime
+    let command_names = context.command_names.clone();
+    let read = tokio::task::spawn_blocking(move || read_usable_members(&path, &command_names));
+
+    let members = match read.await {
+        Ok(it) => it?,
+        Err(error) => return Err(std::io::Error::other(error).into()),
+    };
+
+    *context.members.write()? = members;
+
+    Ok(true)
+}
+
+fn read_usable_members(path: &Path, command_names: &[String]) -> Result<Members> {
+    let mut members = read_members(path)?;
+    let hashed = members.hash_plaintext_passwords()?;
+
+    if hashed > 0 {
+        warn!("Hashed {} plaintext passwords in {:?} in memory only, the file is left as is", hashed, path);
+    }
+
+    if let Err(message) = members.check(command_names) {
+        return misconfiguration(&format!("Unusable members in {:?} > {}", path, message))
+    }
+
+    Ok(members)
+}
+
+async fn reload_or_keep_old(context: &SusContext) {
+    match reload_members(context).await {
+        Ok(true) => info!("Reloaded the members"),
+        Ok(false) => {}
+        Err(error) => warn!("Keeping the old members, can't reload them > {}", error),
+    }
+}
+
+pub fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|it| it.modified()).ok()
+}
+
+/// Reloads the members whenever their file changes.
+pub async fn reload_members_on_changes(context: SusContext, shutdown: Shutdown) {
+    let path = match &context.members_file {
+        Some(it) => it.clone(),
+        None => return,
+    };
+
+    let mut interval = tokio::time::interval(MEMBERS_POLL_PERIOD);
+    let mut last_modified = modified_at(&path);
+
+    let stopped = shutdown.wait();
+    tokio::pin!(stopped);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = &mut stopped => return,
+        }
+
+        let modified = modified_at(&path);
+
+        if modified == last_modified {
+            continue
+        }
+
+        reload_or_keep_old(&context).await;
+        last_modified = modified;
+    }
+}
+
+/// Reloads the members on SIGHUP.
+pub async fn reload_members_on_hangup(context: SusContext, shutdown: Shutdown) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(it) => it,
+            Err(error) => {
+                warn!("Can't listen for SIGHUP > {}", error);
+                return
+            }
+        };
+
+        let stopped = shutdown.wait();
+        tokio::pin!(stopped);
+
+        loop {
+            tokio::select! {
+                _ = hangups.recv() => reload_or_keep_old(&context).await,
+                _ = &mut stopped => return,
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (context, shutdown);
+    }
+}
@@ -12,6 +12,7 @@ use openssl::ssl::SslAcceptorBuilder;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::net::SocketAddr;
+use std::net::TcpListener as StdTcpListener;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use swagger::{Has, XSpanIdString};
@@ -25,13 +26,29 @@ use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod};
 use openapi_client::models;
 
 use std::path::{Path, PathBuf};
-use std::time::{Duration};
+use std::time::{Duration, Instant, SystemTime};
 use std::io::{Write};
 use std::thread;
 
+use crate::audit::{self, AuditLog, AuditWriter, Call, outcome_of};
+use crate::commands::{Commands, notify};
+use crate::config::{Config};
+use crate::events::{Events, LONG_POLL_TIMEOUT};
+use crate::jail::{Jail};
+use crate::legacy;
+use crate::lockout::{Lockout, LockoutPolicy, SystemClock};
+use crate::meetings::{self, MeetingRules, run_meetings};
+use crate::tasks::{TaskRules, run_tasks};
 use crate::members::{load_members, Members};
+use crate::problems::{Failure};
 use crate::messages::{ClientMessage, ServerMessage};
+use crate::game::{Game, GameState};
+use crate::reload::{reload_members_on_changes, reload_members_on_hangup};
+use crate::sessions::{self, Activity, SessionLimits, reap_expired_sessions};
+use crate::shutdown::{self, Shutdown, Trigger, trigger_on_signals};
+use crate::snapshot::{self, save_snapshots_periodically};
 
+use common::helpers::{misconfiguration};
 use common::{
     Result,
     with_error_report,
@@ -43,94 +60,485 @@ use common::parsing::{self, read_message};
 use common::shared::{IntoShared, Shared};
 use common::shared::vec::{SharedVec};
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-/// Builds an SSL implementation for Simple HTTPS from some hard-coded file names
-pub async fn create(addr: &str, https: bool) {
-    let addr = addr.parse().expect("Failed to parse bind address");
+/// Starts everything the `config` asks for and runs
+/// until SIGINT or SIGTERM. The config is expected to
+/// be validated already.
+pub async fn create(config: Config, commands: Commands) {
+    let handle = ServerBuilder::new(config)
+        .commands(commands)
+        .handle_signals()
+        .start()
+        .await
+        .expect("Failed to start the server");
+
+    handle.stopped().await
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "ios")))]
+type TlsAcceptor = Arc<SslAcceptor>;
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "ios"))]
+type TlsAcceptor = ();
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "ios")))]
+fn tls_acceptor(config: &Config) -> Result<TlsAcceptor> {
+    let result = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())
+        .and_then(|mut ssl| {
+            // Server authentication
+            ssl.set_private_key_file(&config.tls.key, SslFiletype::PEM)?;
+            ssl.set_certificate_chain_file(&config.tls.chain)?;
+            ssl.check_private_key()?;
+            Ok(ssl)
+        });
+
+    match result {
+        Ok(it) => Ok(Arc::new(it.build())),
+        Err(error) => misconfiguration(&format!("Can't set up TLS > {}", error)),
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "ios"))]
+fn tls_acceptor(_config: &Config) -> Result<TlsAcceptor> {
+    misconfiguration("HTTPS is not implemented for MacOS, Windows or iOS")
+}
+
+fn bind(listener: Option<StdTcpListener>, address: Result<SocketAddr>) -> Result<StdTcpListener> {
+    match listener {
+        Some(it) => Ok(it),
+        None => Ok(StdTcpListener::bind(address?)?),
+    }
+}
+
+/// Sets up the server, allowing to pass things
+/// in directly instead of reading them from
+/// the files named in the config.
+pub struct ServerBuilder {
+    config: Config,
+    members: Option<Members>,
+    commands: Commands,
+    listener: Option<StdTcpListener>,
+    legacy_listener: Option<StdTcpListener>,
+    handles_signals: bool,
+}
+
+impl ServerBuilder {
+    pub fn new(config: Config) -> Self {
+        ServerBuilder {
+            config,
+            members: None,
+            commands: Commands::builtin(),
+            listener: None,
+            legacy_listener: None,
+            handles_signals: false,
+        }
+    }
+
+    /// Used instead of the members file. The changes
+    /// made by the admins are then kept in memory.
+    pub fn members(mut self, members: Members) -> Self {
+        self.members = Some(members);
+        self
+    }
+
+    pub fn commands(mut self, commands: Commands) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Serves the API here instead of
+    /// binding `config.address`.
+    pub fn listener(mut self, listener: StdTcpListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    /// Serves the NetLab3 terminals here instead
+    /// of binding `config.legacy_address`.
+    pub fn legacy_listener(mut self, listener: StdTcpListener) -> Self {
+        self.legacy_listener = Some(listener);
+        self
+    }
+
+    /// Makes SIGINT and SIGTERM stop the server
+    /// and SIGHUP reload the members file.
+    pub fn handle_signals(mut self) -> Self {
+        self.handles_signals = true;
+        self
+    }
+
+    /// Must be called from within the tokio runtime.
+    pub async fn start(self) -> Result<ServerHandle> {
+        let config = self.config;
+
+        let (members, members_file) = match self.members {
+            Some(it) => (it, None),
+            None => (load_members(&config.members)?, Some(config.members.clone())),
+        };
+
+        if let Err(message) = members.check(&self.commands.qualified_names()) {
+            return misconfiguration(&format!("Unusable members > {}", message))
+        }
+
+        let jail = config.jail()?;
+        let listener = bind(self.listener, config.address())?;
+        let legacy_listener = bind(self.legacy_listener, config.legacy_address())?;
+
+        let addr = listener.local_addr()?;
+        let legacy_addr = legacy_listener.local_addr()?;
+
+        let tls = match config.https {
+            true => Some(tls_acceptor(&config)?),
+            false => None,
+        };
+
+        let context = new_context(
+            jail,
+            config.rules(),
+            AuditLog::new(config.audit()),
+            members,
+            members_file,
+            &self.commands,
+        );
+        let commands = Arc::new(self.commands);
+        let snapshot = config.snapshot();
+
+        snapshot::restore_or_start_over(&context, &snapshot).await;
+
+        let (trigger, shutdown) = shutdown::channel();
+
+        tokio::spawn(reap_expired_sessions(context.clone(), shutdown.clone()));
+        tokio::spawn(run_meetings(context.clone(), shutdown.clone()));
+        tokio::spawn(run_tasks(context.clone(), shutdown.clone()));
+        tokio::spawn(save_snapshots_periodically(context.clone(), snapshot.path.clone(), shutdown.clone()));
+
+        tokio::spawn(reload_members_on_changes(context.clone(), shutdown.clone()));
+
+        if self.handles_signals {
+            tokio::spawn(trigger_on_signals(trigger.clone()));
+            tokio::spawn(reload_members_on_hangup(context.clone(), shutdown.clone()));
+        }
+
+        let legacy = legacy::listen(
+            legacy_listener,
+            context.clone(),
+            commands.clone(),
+            config.legacy_codec(),
+            shutdown.clone(),
+        )?;
+
+        info!("Serving the API at {}", addr);
+
+        let finished = tokio::spawn(run(context, commands, listener, tls, legacy, snapshot.path, shutdown));
+
+        Ok(ServerHandle {
+            addr,
+            legacy_addr,
+            trigger,
+            finished,
+        })
+    }
+}
+
+/// The running server. Dropping the handle
+/// leaves the server running, `shutdown()`
+/// is the way to stop it.
+pub struct ServerHandle {
+    addr: SocketAddr,
+    legacy_addr: SocketAddr,
+    trigger: Trigger,
+    finished: tokio::task::JoinHandle<()>,
+}
+
+impl ServerHandle {
+    /// The actual address, useful when
+    /// binding to port 0.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn legacy_addr(&self) -> SocketAddr {
+        self.legacy_addr
+    }
+
+    /// Asks the server to stop
+    /// without waiting for it.
+    pub fn trigger_shutdown(&self) {
+        self.trigger.trigger()
+    }
+
+    /// Resolves once the server has stopped
+    /// and the sessions have been saved.
+    pub async fn stopped(self) {
+        let _ = self.finished.await;
+    }
+
+    /// Stops accepting, lets the requests in progress
+    /// finish and saves the sessions.
+    pub async fn shutdown(self) {
+        self.trigger_shutdown();
+        self.stopped().await
+    }
+}
+
+/// Serves until the `shutdown`, then
+/// tidies up.
+async fn run(
+    context: SusContext,
+    commands: Arc<Commands>,
+    listener: StdTcpListener,
+    tls: Option<TlsAcceptor>,
+    legacy: legacy::LegacyListener,
+    snapshot_path: PathBuf,
+    shutdown: Shutdown,
+) {
+    {
+        // Long polls shouldn't hold the shutdown
+        let events = context.events.clone();
+        let shutdown = shutdown.clone();
+
+        tokio::spawn(async move {
+            shutdown.wait().await;
+            with_error_report(|| events.interrupt());
+        });
+    }
+
+    let result = serve_api(context.clone(), commands, listener, tls, shutdown).await;
+    with_error_report(|| result);
+
+    legacy.stopped().await;
+
+    match snapshot::save_snapshot(&context, &snapshot_path).await {
+        Ok(count) => info!("Saved {} sessions to {:?}", count, snapshot_path),
+        Err(error) => println!("Error > {}", error),
+    }
+
+    context.audit.flush().await;
+}
 
-    let server = Server::new();
+/// Serves the API until the `shutdown`, and then
+/// lets the requests in progress finish.
+async fn serve_api(
+    context: SusContext,
+    commands: Arc<Commands>,
+    listener: StdTcpListener,
+    tls: Option<TlsAcceptor>,
+    shutdown: Shutdown,
+) -> Result<()> {
+    let server = Server {
+        marker: PhantomData,
+        context,
+        commands,
+    };
 
     let service = MakeService::new(server);
 
     let service = MakeAllowAllAuthenticator::new(service, "cosmo");
 
-    let service =
+    let mut service =
         openapi_client::server::context::MakeAddContext::<_, EmptyContext>::new(
             service
         );
 
-    if https {
+    if let Some(tls_acceptor) = tls {
         #[cfg(any(target_os = "macos", target_os = "windows", target_os = "ios"))]
         {
+            let _ = tls_acceptor;
             unimplemented!("SSL is not implemented for the examples on MacOS, Windows or iOS");
         }
 
         #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "ios")))]
         {
-            let mut ssl = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).expect("Failed to create SSL Acceptor");
-
-            // Server authentication
-            ssl.set_private_key_file("examples/server-key.pem", SslFiletype::PEM).expect("Failed to set private key");
-            ssl.set_certificate_chain_file("examples/server-chain.pem").expect("Failed to set certificate chain");
-            ssl.check_private_key().expect("Failed to check private key");
+            listener.set_nonblocking(true)?;
 
-            let tls_acceptor = Arc::new(ssl.build());
-            let mut tcp_listener = TcpListener::bind(&addr).await.unwrap();
+            let mut tcp_listener = TcpListener::from_std(listener)?;
             let mut incoming = tcp_listener.incoming();
 
-            while let (Some(tcp), rest) = incoming.into_future().await {
+            // Every connection holds a sender, so the
+            // receiver yields `None` once they're all done
+            let (connections, mut drained) = tokio::sync::mpsc::channel::<()>(1);
+
+            let stopped = shutdown.clone().wait();
+            tokio::pin!(stopped);
+
+            loop {
+                let (tcp, rest) = tokio::select! {
+                    it = incoming.into_future() => it,
+                    _ = &mut stopped => break,
+                };
+
+                let tcp = match tcp {
+                    Some(it) => it,
+                    None => break,
+                };
+
                 if let Ok(tcp) = tcp {
                     let addr = tcp.peer_addr().expect("Unable to get remote address");
                     let service = service.call(addr);
                     let tls_acceptor = Arc::clone(&tls_acceptor);
+                    let shutdown = shutdown.clone();
+                    let connection = connections.clone();
 
                     tokio::spawn(async move {
-                        let tls = tokio_openssl::accept(&*tls_acceptor, tcp).await.map_err(|_| ())?;
+                        let _connection = connection;
+
+                        let tls = tokio_openssl::accept(&tls_acceptor, tcp).await.map_err(|_| ())?;
 
                         let service = service.await.map_err(|_| ())?;
 
-                        Http::new().serve_connection(tls, service).await.map_err(|_| ())
+                        let serving = Http::new().serve_connection(tls, service);
+                        tokio::pin!(serving);
+
+                        let result = tokio::select! {
+                            it = &mut serving => it,
+                            _ = shutdown.wait() => {
+                                serving.as_mut().graceful_shutdown();
+                                serving.await
+                            }
+                        };
+
+                        result.map_err(|_| ())
                     });
                 }
 
                 incoming = rest;
             }
+
+            drop(connections);
+            drained.recv().await;
         }
     } else {
         // Using HTTP
-        hyper::server::Server::bind(&addr).serve(service).await.unwrap()
+        let result = match hyper::server::Server::from_tcp(listener) {
+            Ok(it) => it.serve(service).with_graceful_shutdown(shutdown.wait()).await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            return misconfiguration(&format!("Can't serve the API > {}", error))
+        }
     }
+
+    Ok(())
 }
 
-struct UserData {
-    name: String,
-    location: PathBuf,
-    identity: String,
+/// How a session has become a ghost.
+#[derive(Clone, Debug)]
+pub struct Death {
+    pub killer: String,
+    pub killed_at: DateTime<Utc>,
 }
 
-type User = Shared<UserData>;
+#[derive(Clone, Debug)]
+pub struct UserData {
+    pub name: String,
+    pub location: PathBuf,
+    pub identity: String,
+    /// Tells apart the sessions of the same member
+    /// without giving away their identities. Given
+    /// out by the `SessionStore`, see `insert()`.
+    pub tag: String,
+    pub created_at: Instant,
+    pub last_activity: Instant,
+    /// Killed sessions stay around as
+    /// ghosts, see `members::GHOST_ROLE`.
+    pub death: Option<Death>,
+    /// For the kill cooldown, see `members::Role`.
+    pub last_kill: Option<Instant>,
+}
+
+impl UserData {
+    pub fn is_ghost(&self) -> bool {
+        self.death.is_some()
+    }
+}
 
 #[derive(Clone)]
-struct SusContext {
-    users: SharedVec<User>,
-    members: Shared<Members>,
+pub struct SusContext {
+    /// Owns the sessions, locations
+    /// and everything else that changes.
+    pub game: Game,
+    pub members: Shared<Members>,
+    /// Where the changes to the members are
+    /// saved, `None` keeps them in memory.
+    pub members_file: Option<PathBuf>,
+    /// When the admins have last saved the
+    /// `members_file`, so it's not reloaded.
+    pub members_saved_at: Shared<Option<SystemTime>>,
+    /// Held by whoever changes the `members`, so that the
+    /// changes apply one by one while the `members` lock
+    /// is only taken to swap the result in.
+    pub members_changing: Arc<tokio::sync::Mutex<()>>,
+    /// The roles may only allow these, qualified
+    /// with their groups, like `fs.ls`.
+    pub command_names: Arc<Vec<String>>,
+    pub jail: Arc<Jail>,
+    pub limits: SessionLimits,
+    pub events: Events,
+    /// Slows down the password guessing.
+    pub lockout: Shared<Lockout>,
+    pub audit: AuditWriter,
+    pub meetings: MeetingRules,
+    pub tasks: Arc<TaskRules>,
+}
+
+/// Everything the `Config` decides about
+/// the sessions and the game itself.
+#[derive(Clone, Debug, Default)]
+pub struct GameRules {
+    pub limits: SessionLimits,
+    pub lockout: LockoutPolicy,
+    pub meetings: MeetingRules,
+    pub tasks: TaskRules,
 }
 
 #[derive(Clone)]
 pub struct Server<C> {
     marker: PhantomData<C>,
     context: SusContext,
+    commands: Arc<Commands>,
+}
+
+pub(crate) fn new_context(
+    jail: Jail,
+    rules: GameRules,
+    audit: AuditLog,
+    members: Members,
+    members_file: Option<PathBuf>,
+    commands: &Commands,
+) -> SusContext {
+    SusContext {
+        game: Game::spawn(GameState::new()),
+        members: members.to_shared(),
+        members_file,
+        members_saved_at: None.to_shared(),
+        members_changing: Arc::new(tokio::sync::Mutex::new(())),
+        command_names: Arc::new(commands.qualified_names()),
+        jail: Arc::new(jail),
+        limits: rules.limits,
+        events: Events::new(),
+        lockout: Lockout::new(rules.lockout, Arc::new(SystemClock)).to_shared(),
+        audit: AuditWriter::spawn(audit),
+        meetings: rules.meetings,
+        tasks: Arc::new(rules.tasks),
+    }
 }
 
 impl<C> Server<C> {
-    pub fn new() -> Self {
+    pub fn new(jail: Jail, limits: SessionLimits, members: Members, commands: Commands) -> Self {
         Server{
             marker: PhantomData,
-            context: SusContext {
-                users: vec![].to_shared(),
-                members: load_members().expect("Can't load members").to_shared(),
-            },
+            context: new_context(
+                jail,
+                GameRules { limits, ..GameRules::default() },
+                AuditLog::disabled(),
+                members,
+                None,
+                &commands,
+            ),
+            commands: Arc::new(commands),
         }
     }
 }
@@ -138,6 +546,8 @@ impl<C> Server<C> {
 
 use openapi_client::{
     Api,
+    GetEventsResponse,
+    GetMeetingResponse,
     GetMyselfResponse,
     GetNewUserResponse,
     PostQueryResponse,
@@ -146,308 +556,217 @@ use openapi_client::server::MakeService;
 use std::error::Error;
 use swagger::ApiError;
 
-fn notify(message: &str) -> models::Notification {
-    models::Notification {
-        message: message.to_owned()
-    }
-}
-
-fn get_my_data<C>(
+async fn get_my_data<C>(
     server: &Server<C>,
     context: &C
-) -> std::result::Result<Option<User>, ApiError>
+) -> std::result::Result<Option<UserData>, Failure>
 where
     C: Has<Option<swagger::AuthData>>,
 {
-    let maybe_auth = (context as &dyn Has<Option<swagger::AuthData>>).get();
-
-    let auth = match maybe_auth {
+    let sus = match get_identity(context) {
         Some(it) => it,
-        None => return Err("Generic failure".into()),
-    };
-
-    let sus = match auth {
-        swagger::AuthData::ApiKey(key) => key,
-        _ => return Err("Generic failure".into()),
+        None => return Err(missing_identity()),
     };
 
-    let locked_users = match server.context.users.write() {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    for it in locked_users.iter() {
-        let user_lock = match it.read() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
-        };
+    let key = sus.clone();
+    let result = server.context.game.call(move |state| state.sessions.get(&key).cloned()).await;
 
-        if &user_lock.identity == sus {
-            return Ok(Some(it.clone()));
-        }
+    match result {
+        Ok(it) => Ok(it),
+        Err(error) => Err(error.into()),
     }
-
-    return Ok(None);
 }
 
-fn handle_login<C>(
-    command: Vec<String>,
-    server: &Server<C>,
-    shared_me: User,
-) -> std::result::Result<PostQueryResponse, ApiError> {
-    if command.len() < 3 {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("The command misses some parameters")
-        ))
-    }
-
-    let name = &command[1];
-    let pass = &command[2];
+/// The meeting going on as seen by the `identity`.
+async fn describe_meeting<C>(server: &Server<C>, identity: &str) -> Result<Option<models::Meeting>> {
+    let identity = identity.to_owned();
+    let jail = server.context.jail.clone();
 
-    let members_lock = match server.context.members.read() {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    if !members_lock.has_user(name) {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify(&format!("No such a user > {}", name))
-        ))
-    }
+    server.context.game.call(move |state| {
+        state.current_meeting().map(|it| meetings::describe(it, &identity, &jail, Instant::now()))
+    }).await
+}
 
-    let settings = match members_lock.settings_for(name) {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
+/// The router turns away the requests without
+/// the header, but `/user/me` isn't guarded.
+fn missing_identity() -> Failure {
+    Failure::unauthorized("missing_identity", "The Identity header is required")
+}
 
-    if pass != &settings.pass {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("Incorrect password")
-        ))
-    }
+fn span_id<C>(context: &C) -> String
+where
+    C: Has<XSpanIdString>,
+{
+    (context as &dyn Has<XSpanIdString>).get().0.clone()
+}
 
-    match shared_me.write() {
-        Ok(mut it) => {
-            it.name = name.clone();
-        }
-        Err(error) => return Err(format!("{}", error).into())
+fn get_identity<C>(context: &C) -> Option<&String>
+where
+    C: Has<Option<swagger::AuthData>>,
+{
+    match (context as &dyn Has<Option<swagger::AuthData>>).get() {
+        Some(swagger::AuthData::ApiKey(key)) => Some(key),
+        _ => None,
     }
-
-    let role = match members_lock.role_for(name) {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    let message = models::Role {
-        title: role.title,
-        allowed_commands: role.allowed_commands,
-    };
-
-    Ok(PostQueryResponse::HereIsANewRoleForYou(message))
 }
 
-fn handle_ls<C>(
-    _command: Vec<String>,
-    _server: &Server<C>,
-    shared_me: User,
-) -> std::result::Result<PostQueryResponse, ApiError> {
-    let location = match shared_me.read() {
-        Ok(it) => it.location.clone(),
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    let mut files = vec![];
-
-    let contents = match std::fs::read_dir(location) {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    for it in contents {
-        let that = match it {
-            Ok(value) => value,
-            Err(error) => return Err(format!("{}", error).into())
+/// The handlers behind the `Api`, which
+/// adds the audit records on top.
+impl<C> Server<C>
+where
+    C: Has<XSpanIdString> + Has<Option<swagger::Authorization>> + Has<Option<swagger::AuthData>> + Send + Sync
+{
+    async fn serve_get_myself(
+        &self,
+        context: &C) -> std::result::Result<GetMyselfResponse, Failure>
+    {
+        let me = match get_my_data(self, context).await? {
+            Some(it) => it,
+            None => return Err(Failure::unknown_identity()),
         };
 
-        match that.path().to_str() {
-            Some(thing) => files.push(thing.to_owned()),
-            None => {}
-        }
-    }
-
-    let message = models::FilesList { files };
-    return Ok(PostQueryResponse::HereAreTheFiles(message))
-}
-
-fn location_to_string(location: &PathBuf) -> Result<String> {
-    match location.to_str() {
-        Some(thing) => Ok(thing.to_owned().replace("\\\\?\\", "")),
-        None => Ok("Unknown".to_owned())
-    }
-}
+        let mut is_alive = false;
+        let mut remaining_lifetime = None;
+        let now = Instant::now();
 
-fn handle_cd<C>(
-    command: Vec<String>,
-    _server: &Server<C>,
-    shared_me: User,
-) -> std::result::Result<PostQueryResponse, ApiError> {
-    if command.len() < 2 {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("The command misses some parameters")
-        ))
-    }
+        if !self.context.limits.is_expired(&me, now) {
+            let remaining = self.context.limits.remaining_lifetime(&me, now);
+            is_alive = !me.is_ghost();
+            remaining_lifetime = Some(remaining.as_secs());
+        }
 
-    let target = &command[1];
-    let target_path = Path::new(target);
+        let meeting = match describe_meeting(self, &me.identity).await {
+            Ok(it) => it,
+            Err(error) => return Err(error.into()),
+        };
 
-    if !target_path.exists() {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("No such a path")
-        ))
-    }
+        let response = models::InlineResponse2001 {
+            is_alive,
+            remaining_lifetime,
+            killed_by: me.death.as_ref().map(|it| it.killer.clone()),
+            killed_at: me.death.as_ref().map(|it| it.killed_at),
+            meeting,
+        };
 
-    if !target_path.is_dir() {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("This is not a directory")
-        ))
+        Ok(GetMyselfResponse::HereIsYourStatus(response))
     }
 
-    let mut new_location: PathBuf;
+    async fn serve_get_meeting(
+        &self,
+        context: &C) -> std::result::Result<GetMeetingResponse, Failure>
+    {
+        let sus = match get_identity(context) {
+            Some(it) => it.clone(),
+            None => return Err(missing_identity()),
+        };
 
-    if target_path.is_absolute() {
-        new_location = target_path.to_path_buf();
-    } else {
-        new_location = match shared_me.read() {
-            Ok(it) => it.location.clone(),
-            Err(error) => return Err(format!("{}", error).into())
+        let key = sus.clone();
+        let is_known = match self.context.game.call(move |state| state.sessions.contains(&key)).await {
+            Ok(it) => it,
+            Err(error) => return Err(error.into()),
         };
-        new_location.push(target);
-    }
 
-    let normalized = match new_location.canonicalize() {
-        Ok(it) => it,
-        Err(error) => {
-            return Ok(PostQueryResponse::SomeRandomInformation(
-                notify(&format!("Error > {:?}", error))
-            ))
+        if !is_known {
+            return match sessions::has_expired(&self.context, &sus).await {
+                Ok(true) => Ok(GetMeetingResponse::YourSessionHasExpired),
+                Ok(false) => Err(Failure::unknown_identity()),
+                Err(error) => Err(error.into()),
+            }
         }
-    };
 
-    match shared_me.write() {
-        Ok(mut it) => {
-            it.location = normalized.clone();
+        match describe_meeting(self, &sus).await {
+            Ok(Some(it)) => Ok(GetMeetingResponse::HereIsTheMeeting(it)),
+            Ok(None) => Ok(GetMeetingResponse::ThereIsNoMeeting),
+            Err(error) => Err(error.into()),
         }
-        Err(error) => return Err(format!("{}", error).into())
     }
 
-    let message = models::MoveTo {
-        location: match location_to_string(&normalized) {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
-        }
-    };
-
-    Ok(PostQueryResponse::HereIsTheNewLocation(message))
-}
+    async fn serve_get_new_user(
+        &self,
+        _context: &C) -> std::result::Result<GetNewUserResponse, Failure>
+    {
+        let guest_role = match self.context.members.read() {
+            Ok(it) => match it.role_for("guest", &self.context.command_names) {
+                Ok(that) => that,
+                Err(error) => return Err(error.into()),
+            },
+            Err(error) => return Err(error.into()),
+        };
 
-fn collect_users<C>(
-    server: &Server<C>,
-) -> Result<Vec<models::UsersListUsers>> {
-    let mut users = vec![];
+        let role_data = models::Role {
+            title: guest_role.title,
+            allowed_commands: guest_role.allowed_commands,
+        };
 
-    let users_lock = server.context.users.read()?;
+        let user = match sessions::start_guest_session(&self.context).await {
+            Ok(it) => it,
+            Err(error) => return Err(error.into()),
+        };
 
-    for it in users_lock.iter() {
-        let name = it.read()?.name.clone();
-        let location = it.read()?.location.clone();
-        let string = location_to_string(&location)?;
+        let location_data = models::MoveTo {
+            location: self.context.jail.to_virtual(&user.location),
+        };
 
-        let data = models::UsersListUsers {
-            user: name,
-            location: string,
+        let resposnse = models::InlineResponse200 {
+            role: role_data,
+            location: location_data,
+            identity: user.identity,
         };
 
-        users.push(data);
+        Ok(GetNewUserResponse::HereIsTheDefaultIdentity(resposnse))
     }
 
-    Ok(users)
-}
-
-fn handle_who<C>(
-    _command: Vec<String>,
-    server: &Server<C>,
-    _shared_me: User,
-) -> std::result::Result<PostQueryResponse, ApiError> {
-    let users = match collect_users(server) {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
+    async fn serve_post_query(
+        &self,
+        request_body: models::Query,
+        context: &C) -> std::result::Result<PostQueryResponse, Failure>
+    {
+        let command = &request_body.arguments;
 
-    let message = models::UsersList { users };
+        if command.is_empty() {
+            return Err(Failure::bad_request("empty_command", "Empty command"))
+        }
 
-    Ok(PostQueryResponse::HereAreYourCrewmates(message))
-}
+        let sus = match get_identity(context) {
+            Some(it) => it,
+            None => return Err(missing_identity()),
+        };
 
-fn are_locations_same(a: &PathBuf, b: &PathBuf) -> Result<bool> {
-    let string_a = location_to_string(a)?;
-    let string_b = location_to_string(b)?;
-    Ok(string_a == string_b)
-}
+        let me = match sessions::touch(&self.context, sus, Instant::now()).await {
+            Ok(Activity::Alive(it)) => it,
+            Ok(Activity::Expired) => return Ok(PostQueryResponse::YourSessionHasExpired),
+            Ok(Activity::Gone) => return Err(Failure::unknown_identity()),
+            Err(error) => return Err(error.into()),
+        };
 
-fn handle_kill<C>(
-    command: Vec<String>,
-    server: &Server<C>,
-    shared_me: User,
-) -> std::result::Result<PostQueryResponse, ApiError> {
-    if command.len() < 2 {
-        return Ok(PostQueryResponse::SomeRandomInformation(
-            notify("The command misses some parameters")
-        ))
+        self.commands.execute(command.clone(), &self.context, me).await
     }
 
-    let target = &command[1];
-
-    let mut users_lock = match server.context.users.write() {
-        Ok(it) => it,
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    let mut users_to_kill = vec![];
-
-    let my_location = match shared_me.read() {
-        Ok(it) => it.location.clone(),
-        Err(error) => return Err(format!("{}", error).into())
-    };
-
-    for (index, raw_it) in users_lock.iter().enumerate() {
-        let it = match raw_it.write() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
+    async fn serve_get_events(
+        &self,
+        context: &C) -> std::result::Result<GetEventsResponse, Failure>
+    {
+        let sus = match get_identity(context) {
+            Some(it) => it.clone(),
+            None => return Err(missing_identity()),
         };
 
-        let has_same_name = &it.name == target;
-        let is_nearby = match are_locations_same(&it.location, &my_location) {
+        let events = match self.context.events.wait(&sus, LONG_POLL_TIMEOUT).await {
             Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
+            Err(error) => return Err(error.into()),
         };
 
-        if has_same_name && is_nearby {
-            users_to_kill.push(index);
+        if let Some(it) = events {
+            let message = models::EventsList { events: it };
+            return Ok(GetEventsResponse::HereIsWhatHappened(message))
         }
-    }
-
-    let mut shift = 0;
 
-    for it in &users_to_kill {
-        users_lock.remove(it - shift);
-        shift += 1;
+        match sessions::has_expired(&self.context, &sus).await {
+            Ok(true) => Ok(GetEventsResponse::YourSessionHasExpired),
+            Ok(false) => Err(Failure::unknown_identity()),
+            Err(error) => Err(error.into()),
+        }
     }
-
-    let message = models::KillResult {
-        killed_users_count: users_to_kill.len() as u32,
-    };
-
-    Ok(PostQueryResponse::HereIsTheKillResult(message))
 }
 
 #[async_trait]
@@ -455,107 +774,55 @@ impl<C> Api<C> for Server<C>
 where
     C: Has<XSpanIdString> + Has<Option<swagger::Authorization>> + Has<Option<swagger::AuthData>> + Send + Sync
 {
-    /// Returns your status
-    async fn get_myself(
+    /// Wait for something to happen
+    async fn get_events(
         &self,
-        context: &C) -> std::result::Result<GetMyselfResponse, ApiError>
+        context: &C) -> std::result::Result<GetEventsResponse, ApiError>
     {
-        let context = context.clone();
-
-        let maybe_auth = (context as &dyn Has<Option<swagger::AuthData>>).get();
-
-        let auth = match maybe_auth {
-            Some(it) => it,
-            None => return Err("Generic failure".into()),
-        };
-
-        let sus = match auth {
-            swagger::AuthData::ApiKey(key) => key,
-            _ => return Err("Generic failure".into()),
-        };
-
-        let locked_users = match self.context.users.write() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
-        };
-
-        let mut is_alive = false;
+        Ok(self.serve_get_events(context).await.unwrap_or_else(GetEventsResponse::from))
+    }
 
-        for it in locked_users.iter() {
-            let user_lock = match it.read() {
-                Ok(it) => it,
-                Err(error) => return Err(format!("{}", error).into())
-            };
+    /// Returns the meeting going on
+    async fn get_meeting(
+        &self,
+        context: &C) -> std::result::Result<GetMeetingResponse, ApiError>
+    {
+        let identity = get_identity(context).cloned();
+        let call = Call::start(&self.context, Some(span_id(context)), "get_meeting", identity, vec![]).await;
 
-            if &user_lock.identity == sus {
-                is_alive = true;
-                break
-            }
-        }
+        let response = self.serve_get_meeting(context).await.unwrap_or_else(GetMeetingResponse::from);
+        audit::record(&self.context, call, outcome_of(&response));
+        Ok(response)
+    }
 
-        let response = models::InlineResponse2001 {
-            is_alive: is_alive,
-        };
+    /// Returns your status
+    async fn get_myself(
+        &self,
+        context: &C) -> std::result::Result<GetMyselfResponse, ApiError>
+    {
+        let identity = get_identity(context).cloned();
+        let call = Call::start(&self.context, Some(span_id(context)), "get_myself", identity, vec![]).await;
 
-        Ok(GetMyselfResponse::HereIsYourStatus(response))
+        let response = self.serve_get_myself(context).await.unwrap_or_else(GetMyselfResponse::from);
+        audit::record(&self.context, call, outcome_of(&response));
+        Ok(response)
     }
 
     /// Get the initial user context
     async fn get_new_user(
         &self,
-        _context: &C) -> std::result::Result<GetNewUserResponse, ApiError>
+        context: &C) -> std::result::Result<GetNewUserResponse, ApiError>
     {
-        let the_members = match self.context.members.read() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        let guest_role = match the_members.role_for("guest") {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        let role_data = models::Role {
-            title: guest_role.title,
-            allowed_commands: guest_role.allowed_commands,
-        };
-
-        let directory = match std::env::current_dir() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        let location = match directory.to_str() {
-            Some(thing) => thing.to_owned(),
-            None => return Err("Generic failure".into()),
-        };
+        let mut call = Call::start(&self.context, Some(span_id(context)), "get_new_user", None, vec![]).await;
 
-        let location_data = models::MoveTo {
-            location: location,
-        };
+        let response = self.serve_get_new_user(context).await.unwrap_or_else(GetNewUserResponse::from);
 
-        let identity = Uuid::new_v4().to_hyphenated().to_string();
-
-        let resposnse = models::InlineResponse200 {
-            role: role_data,
-            location: location_data,
-            identity: identity.clone(),
-        };
-
-        let user = UserData {
-            name: format!("guest"),
-            location: directory,
-            identity: identity,
-        }.to_shared();
-
-        let mut locked_users = match self.context.users.write() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into())
-        };
-
-        locked_users.push(user);
+        if let GetNewUserResponse::HereIsTheDefaultIdentity(it) = &response {
+            call.identity = Some(it.identity.clone());
+        }
 
-        Ok(GetNewUserResponse::HereIsTheDefaultIdentity(resposnse))
+        audit::record(&self.context, call, outcome_of(&response));
+        Ok(response)
     }
 
     /// Run a command
@@ -564,50 +831,13 @@ where
         request_body: models::Query,
         context: &C) -> std::result::Result<PostQueryResponse, ApiError>
     {
-        let context = context.clone();
-        let command = &request_body.arguments;
-
-        if command.len() == 0 {
-            return Ok(PostQueryResponse::SomeRandomInformation(notify("Empty command")))
-        }
-
-        let shared_me = match get_my_data(self, context) {
-            Ok(it) => match it {
-                Some(that) => that,
-                None => return Ok(PostQueryResponse::YouAreDead),
-            },
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        let name = match shared_me.read() {
-            Ok(it) => it.name.clone(),
-            Err(error) => return Err(format!("{}", error).into()),
-        };
+        let identity = get_identity(context).cloned();
+        let argv = self.commands.redact(request_body.arguments.clone());
+        let call = Call::start(&self.context, Some(span_id(context)), "post_query", identity, argv).await;
 
-        let the_members = match self.context.members.read() {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        let role = match the_members.role_for(&name) {
-            Ok(it) => it,
-            Err(error) => return Err(format!("{}", error).into()),
-        };
-
-        if !role.allowed_commands.contains(&command[0]) {
-            return Ok(PostQueryResponse::SomeRandomInformation(notify("No such an allowed command for you")))
-        }
-
-        return match command[0].as_ref() as &str {
-            "login" => handle_login(command.clone(), self, shared_me),
-            "ls" => handle_ls(command.clone(), self, shared_me),
-            "cd" => handle_cd(command.clone(), self, shared_me),
-            "who" => handle_who(command.clone(), self, shared_me),
-            "kill" => handle_kill(command.clone(), self, shared_me),
-            it => Ok(PostQueryResponse::SomeRandomInformation(
-                notify(&format!("No such a command > {}", it))
-            ))
-        }
+        let response = self.serve_post_query(request_body, context).await.unwrap_or_else(PostQueryResponse::from);
+        audit::record(&self.context, call, outcome_of(&response));
+        Ok(response)
     }
 
 }
@@ -0,0 +1,180 @@
+use std::time::{Duration, Instant};
+
+use common::{Result, with_error_report};
+
+use uuid::Uuid;
+
+use crate::events::{Events};
+use crate::game::{GameState};
+use crate::server::{SusContext, UserData};
+use crate::shutdown::{Shutdown};
+
+pub mod store;
+
+pub use store::{SessionStore};
+
+/// How often the reaper looks for expired sessions.
+pub const REAPER_PERIOD: Duration = Duration::from_secs(5);
+
+pub const DEFAULT_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+pub const DEFAULT_ABSOLUTE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Copy, Debug)]
+pub struct SessionLimits {
+    /// The session dies if no commands
+    /// are run for this long.
+    pub idle: Duration,
+    /// The session dies this long after it
+    /// was created no matter what.
+    pub absolute: Duration,
+}
+
+impl Default for SessionLimits {
+    fn default() -> Self {
+        SessionLimits {
+            idle: DEFAULT_IDLE_TTL,
+            absolute: DEFAULT_ABSOLUTE_TTL,
+        }
+    }
+}
+
+impl SessionLimits {
+    pub fn deadline(&self, user: &UserData) -> Instant {
+        let idle_deadline = user.last_activity + self.idle;
+        let absolute_deadline = user.created_at + self.absolute;
+        std::cmp::min(idle_deadline, absolute_deadline)
+    }
+
+    pub fn remaining_lifetime(&self, user: &UserData, now: Instant) -> Duration {
+        self.deadline(user).saturating_duration_since(now)
+    }
+
+    pub fn is_expired(&self, user: &UserData, now: Instant) -> bool {
+        self.deadline(user) <= now
+    }
+}
+
+/// What the server knows about the
+/// session of a client.
+pub enum Activity {
+    Alive(UserData),
+    Expired,
+    /// Ended, or has never existed.
+    Gone,
+}
+
+/// Creates a new guest at the root of
+/// the ship and opens its mailbox.
+pub async fn start_guest_session(context: &SusContext) -> Result<UserData> {
+    let identity = Uuid::new_v4().to_hyphenated().to_string();
+
+    context.events.open(&identity)?;
+
+    let now = Instant::now();
+
+    let user = UserData {
+        name: "guest".to_owned(),
+        location: context.jail.root().clone(),
+        identity,
+        tag: String::new(),
+        created_at: now,
+        last_activity: now,
+        death: None,
+        last_kill: None,
+    };
+
+    context.game.call(move |state| state.sessions.insert(user)).await
+}
+
+/// Forgets the session of a client that
+/// has gone away on its own.
+pub async fn end_session(context: &SusContext, identity: &str) -> Result<()> {
+    let key = identity.to_owned();
+    context.game.call(move |state| state.sessions.remove(&key)).await?;
+    context.events.close(identity)
+}
+
+/// Whether the identity belonged to a session
+/// that has been evicted recently.
+pub async fn has_expired(context: &SusContext, identity: &str) -> Result<bool> {
+    let key = identity.to_owned();
+    context.game.call(move |state| state.expired.contains_key(&key)).await
+}
+
+/// Marks the session as active, or evicts it
+/// if it's too late for that.
+pub async fn touch(context: &SusContext, identity: &str, now: Instant) -> Result<Activity> {
+    let identity = identity.to_owned();
+    let limits = context.limits;
+    let events = context.events.clone();
+
+    context.game.call(move |state| {
+        let is_expired = match state.sessions.get(&identity) {
+            Some(it) => limits.is_expired(it, now),
+            None if state.expired.contains_key(&identity) => return Ok(Activity::Expired),
+            None => return Ok(Activity::Gone),
+        };
+
+        if is_expired {
+            evict_expired(state, &limits, &events, now)?;
+            return Ok(Activity::Expired)
+        }
+
+        state.sessions.touch(&identity, now);
+
+        match state.sessions.get(&identity) {
+            Some(it) => Ok(Activity::Alive(it.clone())),
+            None => Ok(Activity::Gone),
+        }
+    }).await?
+}
+
+/// Drops the expired sessions, leaving their
+/// identities in `expired` for the absolute TTL
+/// so that clients can be told what happened.
+pub fn evict_expired(
+    state: &mut GameState,
+    limits: &SessionLimits,
+    events: &Events,
+    now: Instant,
+) -> Result<()> {
+    let identities: Vec<String> = state.sessions.iter()
+        .filter(|it| limits.is_expired(it, now))
+        .map(|it| it.identity.clone())
+        .collect();
+
+    for it in identities {
+        state.sessions.remove(&it);
+        events.close(&it)?;
+        state.expired.insert(it, now);
+    }
+
+    let absolute = limits.absolute;
+    state.expired.retain(|_, evicted_at| now.duration_since(*evicted_at) < absolute);
+
+    Ok(())
+}
+
+pub async fn reap_expired_sessions(context: SusContext, shutdown: Shutdown) {
+    let mut interval = tokio::time::interval(REAPER_PERIOD);
+
+    let stopped = shutdown.wait();
+    tokio::pin!(stopped);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = &mut stopped => return,
+        }
+
+        let limits = context.limits;
+        let events = context.events.clone();
+        let now = Instant::now();
+
+        let result = context.game
+            .call(move |state| evict_expired(state, &limits, &events, now))
+            .await;
+
+        with_error_report(|| result.and_then(|it| it));
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash};
+use std::path::{Path, PathBuf};
+use std::time::{Instant};
+
+use crate::server::{Death, UserData};
+
+/// The sessions, ghosts included, keyed by identity,
+/// with indexes by name and location. The users can
+/// only be changed via the methods here, so that
+/// the indexes stay up to date.
+pub struct SessionStore {
+    by_identity: HashMap<String, UserData>,
+    /// Member names mapped to the identities
+    /// of everyone logged in under them.
+    by_name: HashMap<String, HashSet<String>>,
+    by_location: HashMap<PathBuf, HashSet<String>>,
+    /// Where to look for the next free tag.
+    next_tag: u16,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        SessionStore::new()
+    }
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        SessionStore {
+            by_identity: HashMap::new(),
+            by_name: HashMap::new(),
+            by_location: HashMap::new(),
+            next_tag: 0,
+        }
+    }
+
+    /// Keeps the tag of the `user` if no one else with
+    /// the name has it, otherwise gives out a new one.
+    /// Returns the user as stored.
+    pub fn insert(&mut self, mut user: UserData) -> UserData {
+        let identity = user.identity.clone();
+
+        self.remove(&identity);
+
+        if user.tag.is_empty() || self.is_tag_taken(&user.name, &user.tag, &identity) {
+            user.tag = self.free_tag(&user.name, &identity);
+        }
+
+        link(&mut self.by_name, user.name.clone(), &identity);
+        link(&mut self.by_location, user.location.clone(), &identity);
+
+        self.by_identity.insert(identity, user.clone());
+        user
+    }
+
+    pub fn remove(&mut self, identity: &str) -> Option<UserData> {
+        let user = self.by_identity.remove(identity)?;
+
+        unlink(&mut self.by_name, &user.name, identity);
+        unlink(&mut self.by_location, &user.location, identity);
+
+        Some(user)
+    }
+
+    pub fn get(&self, identity: &str) -> Option<&UserData> {
+        self.by_identity.get(identity)
+    }
+
+    pub fn contains(&self, identity: &str) -> bool {
+        self.by_identity.contains_key(identity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_identity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_identity.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UserData> {
+        self.by_identity.values()
+    }
+
+    pub fn identities_at(&self, location: &Path) -> Vec<String> {
+        match self.by_location.get(location) {
+            Some(it) => it.iter().cloned().collect(),
+            None => vec![],
+        }
+    }
+
+    /// Everyone alive with the `name` at the `location`.
+    pub fn living_named_at(&self, name: &str, location: &Path) -> Vec<UserData> {
+        let identities: Vec<&String> = match (self.by_name.get(name), self.by_location.get(location)) {
+            (Some(named), Some(nearby)) => named.intersection(nearby).collect(),
+            _ => vec![],
+        };
+
+        identities.into_iter()
+            .filter_map(|it| self.by_identity.get(it))
+            .filter(|it| !it.is_ghost())
+            .cloned()
+            .collect()
+    }
+
+    /// Everyone alive with the `name`, wherever they are.
+    pub fn living_named(&self, name: &str) -> Vec<UserData> {
+        let identities = match self.by_name.get(name) {
+            Some(it) => it,
+            None => return vec![],
+        };
+
+        identities.iter()
+            .filter_map(|it| self.by_identity.get(it))
+            .filter(|it| !it.is_ghost())
+            .cloned()
+            .collect()
+    }
+
+    /// Turns the user into a ghost if it's
+    /// still alive and returns it.
+    pub fn kill(&mut self, identity: &str, death: &Death) -> Option<UserData> {
+        let user = match self.by_identity.get_mut(identity) {
+            Some(it) if !it.is_ghost() => it,
+            _ => return None,
+        };
+
+        user.death = Some(death.clone());
+        Some(user.clone())
+    }
+
+    /// Brings the ghosts back to life and stops the
+    /// kill cooldowns. Returns the revived ones.
+    pub fn revive_all(&mut self) -> Vec<UserData> {
+        let mut revived = vec![];
+
+        for it in self.by_identity.values_mut() {
+            it.last_kill = None;
+
+            if it.death.take().is_some() {
+                revived.push(it.clone());
+            }
+        }
+
+        revived
+    }
+
+    /// Starts the kill cooldown.
+    pub fn mark_kill(&mut self, identity: &str, now: Instant) {
+        if let Some(it) = self.by_identity.get_mut(identity) {
+            it.last_kill = Some(now);
+        }
+    }
+
+    pub fn touch(&mut self, identity: &str, now: Instant) {
+        if let Some(it) = self.by_identity.get_mut(identity) {
+            it.last_activity = now;
+        }
+    }
+
+    /// Gives the user a new tag if someone
+    /// else with the `name` has its one.
+    pub fn rename(&mut self, identity: &str, name: &str) {
+        let tag = match self.by_identity.get(identity) {
+            Some(it) if self.is_tag_taken(name, &it.tag, identity) => self.free_tag(name, identity),
+            Some(it) => it.tag.clone(),
+            None => return,
+        };
+
+        let user = match self.by_identity.get_mut(identity) {
+            Some(it) => it,
+            None => return,
+        };
+
+        user.tag = tag;
+        let old = std::mem::replace(&mut user.name, name.to_owned());
+
+        unlink(&mut self.by_name, &old, identity);
+        link(&mut self.by_name, name.to_owned(), identity);
+    }
+
+    pub fn relocate(&mut self, identity: &str, location: &Path) {
+        let user = match self.by_identity.get_mut(identity) {
+            Some(it) => it,
+            None => return,
+        };
+
+        let old = std::mem::replace(&mut user.location, location.to_owned());
+
+        unlink(&mut self.by_location, &old, identity);
+        link(&mut self.by_location, location.to_owned(), identity);
+    }
+
+    /// Whether someone with the `name` other than
+    /// the `identity` goes by the `tag` already.
+    fn is_tag_taken(&self, name: &str, tag: &str, identity: &str) -> bool {
+        let identities = match self.by_name.get(name) {
+            Some(it) => it,
+            None => return false,
+        };
+
+        identities.iter()
+            .filter(|it| it.as_str() != identity)
+            .filter_map(|it| self.by_identity.get(it))
+            .any(|it| it.tag == tag)
+    }
+
+    fn free_tag(&mut self, name: &str, identity: &str) -> String {
+        loop {
+            let tag = format!("{:04x}", self.next_tag);
+            self.next_tag = self.next_tag.wrapping_add(1);
+
+            if !self.is_tag_taken(name, &tag, identity) {
+                return tag
+            }
+        }
+    }
+}
+
+fn link<K: Eq + Hash>(index: &mut HashMap<K, HashSet<String>>, key: K, identity: &str) {
+    index.entry(key).or_default().insert(identity.to_owned());
+}
+
+fn unlink<K, Q>(index: &mut HashMap<K, HashSet<String>>, key: &Q, identity: &str)
+where
+    K: Eq + Hash + std::borrow::Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    let is_empty = match index.get_mut(key) {
+        Some(it) => {
+            it.remove(identity);
+            it.is_empty()
+        }
+        None => false,
+    };
+
+    if is_empty {
+        index.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guest(identity: &str) -> UserData {
+        let now = Instant::now();
+
+        UserData {
+            name: "guest".to_owned(),
+            location: PathBuf::from("/"),
+            identity: identity.to_owned(),
+            tag: String::new(),
+            created_at: now,
+            last_activity: now,
+            death: None,
+            last_kill: None,
+        }
+    }
+
+    #[test]
+    fn tells_apart_the_sessions_with_the_same_name() {
+        let mut store = SessionStore::new();
+
+        let tags: HashSet<String> = (0..1000)
+            .map(|it| store.insert(guest(&it.to_string())).tag)
+            .collect();
+
+        assert_eq!(tags.len(), 1000);
+    }
+
+    #[test]
+    fn keeps_the_tags_apart_on_rename() {
+        let mut store = SessionStore::new();
+
+        let first = store.insert(guest("a"));
+        let second = store.insert(UserData { name: "sam".to_owned(), tag: first.tag.clone(), ..guest("b") });
+        assert_eq!(first.tag, second.tag);
+
+        store.rename("b", "guest");
+        assert_ne!(store.get("a").unwrap().tag, store.get("b").unwrap().tag);
+    }
+
+    #[test]
+    fn keeps_a_free_tag() {
+        let mut store = SessionStore::new();
+
+        let user = store.insert(UserData { tag: "beef".to_owned(), ..guest("a") });
+        assert_eq!(user.tag, "beef");
+    }
+}
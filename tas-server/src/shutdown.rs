@@ -0,0 +1,88 @@
+use std::sync::{Arc};
+
+use log::{info, warn};
+use tokio::sync::{watch};
+
+/// Tells everyone listening to the
+/// matching `Shutdown` to stop.
+#[derive(Clone)]
+pub struct Trigger {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+/// Resolves once the server
+/// is asked to stop.
+#[derive(Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+}
+
+pub fn channel() -> (Trigger, Shutdown) {
+    let (sender, receiver) = watch::channel(false);
+
+    let trigger = Trigger {
+        sender: Arc::new(sender),
+    };
+
+    (trigger, Shutdown { receiver })
+}
+
+impl Trigger {
+    pub fn trigger(&self) {
+        let _ = self.sender.broadcast(true);
+    }
+}
+
+impl Shutdown {
+    pub fn is_triggered(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Only `Trigger::trigger()` counts, dropping
+    /// all the triggers stops nothing.
+    pub async fn wait(self) {
+        let mut receiver = self.receiver;
+
+        while let Some(it) = receiver.recv().await {
+            if it {
+                return
+            }
+        }
+
+        // Nobody is left to ask for it
+        std::future::pending::<()>().await
+    }
+}
+
+/// Pulls the `trigger` on SIGINT or SIGTERM.
+pub async fn trigger_on_signals(trigger: Trigger) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = match signal(SignalKind::terminate()) {
+            Ok(it) => it,
+            Err(error) => {
+                warn!("Can't listen for SIGTERM > {}", error);
+                return
+            }
+        };
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => info!("Got SIGINT, shutting down"),
+            _ = terminate.recv() => info!("Got SIGTERM, shutting down"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            warn!("Can't listen for Ctrl+C > {}", error);
+            return
+        }
+
+        info!("Got Ctrl+C, shutting down");
+    }
+
+    trigger.trigger();
+}
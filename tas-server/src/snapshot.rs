@@ -0,0 +1,286 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+
+use common::helpers::{misconfiguration};
+use common::parsing::{from_bytes, to_bytes};
+use common::serializable;
+use common::{Result, with_error_report};
+
+use crate::game::{GameState};
+use crate::jail::{Jail, Lookup};
+use crate::server::{Death, SusContext, UserData};
+use crate::shutdown::{Shutdown};
+use crate::tasks::{Team};
+
+/// Bump this whenever the records change,
+/// so that old snapshots are not misread.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+pub const SNAPSHOT_FILE: &str = "sessions.bson";
+
+/// How often the sessions are saved
+/// while the server is running.
+pub const SNAPSHOT_PERIOD: Duration = Duration::from_secs(30);
+
+serializable! {
+    pub struct SessionRecord {
+        pub identity: String,
+        pub name: String,
+        /// A new one is given out if someone
+        /// else with the name has it by then.
+        pub tag: String,
+        /// As seen from inside the jail, so that
+        /// moving the root doesn't break it.
+        pub location: String,
+        /// Expired sessions are saved as well,
+        /// so that their clients get told so.
+        pub is_expired: bool,
+        /// Unix time in seconds.
+        pub created_at: i64,
+        /// Unix time in seconds. For the expired
+        /// sessions it's the time of eviction.
+        pub last_activity: i64,
+        /// Set for the ghosts.
+        pub killed_by: Option<String>,
+        pub killed_at: Option<DateTime<Utc>>,
+        /// Seconds since the last kill, so that the
+        /// cooldown goes on where it has stopped.
+        pub last_kill_ago: Option<u64>,
+    }
+
+    pub struct Snapshot {
+        pub version: u32,
+        pub sessions: Vec<SessionRecord>,
+        /// Kept so that the game
+        /// over survives the restarts.
+        pub game_over: Option<Team>,
+    }
+
+    /// Read first to reject unknown
+    /// versions before the rest.
+    struct SnapshotHeader {
+        version: u32,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SnapshotOptions {
+    pub path: PathBuf,
+    /// Whether to pick up the sessions
+    /// saved by the previous run.
+    pub restore: bool,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        SnapshotOptions {
+            path: PathBuf::from(SNAPSHOT_FILE),
+            restore: true,
+        }
+    }
+}
+
+/// Converts `Instant`s to the wall clock and back.
+struct Clock {
+    instant: Instant,
+    unix: i64,
+}
+
+impl Clock {
+    fn now() -> Result<Self> {
+        let unix = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+        Ok(Clock {
+            instant: Instant::now(),
+            unix,
+        })
+    }
+
+    fn to_unix(&self, instant: Instant) -> i64 {
+        self.unix - self.instant.saturating_duration_since(instant).as_secs() as i64
+    }
+
+    /// Times in the future are clamped to now.
+    fn to_instant(&self, unix: i64) -> Instant {
+        self.ago((self.unix - unix).max(0) as u64)
+    }
+
+    fn ago(&self, seconds: u64) -> Instant {
+        let ago = Duration::from_secs(seconds);
+        self.instant.checked_sub(ago).unwrap_or(self.instant)
+    }
+}
+
+fn take_snapshot(state: &GameState, jail: &Jail, clock: &Clock) -> Snapshot {
+    let mut sessions = vec![];
+
+    for it in state.sessions.iter() {
+        sessions.push(SessionRecord {
+            identity: it.identity.clone(),
+            name: it.name.clone(),
+            tag: it.tag.clone(),
+            location: jail.to_virtual(&it.location),
+            is_expired: false,
+            created_at: clock.to_unix(it.created_at),
+            last_activity: clock.to_unix(it.last_activity),
+            killed_by: it.death.as_ref().map(|that| that.killer.clone()),
+            killed_at: it.death.as_ref().map(|that| that.killed_at),
+            last_kill_ago: it.last_kill.map(|that| clock.instant.saturating_duration_since(that).as_secs()),
+        });
+    }
+
+    for (identity, evicted_at) in &state.expired {
+        sessions.push(SessionRecord {
+            identity: identity.clone(),
+            name: String::new(),
+            tag: String::new(),
+            location: String::new(),
+            is_expired: true,
+            created_at: clock.to_unix(*evicted_at),
+            last_activity: clock.to_unix(*evicted_at),
+            killed_by: None,
+            killed_at: None,
+            last_kill_ago: None,
+        });
+    }
+
+    Snapshot {
+        version: SNAPSHOT_VERSION,
+        sessions,
+        game_over: state.game_over,
+    }
+}
+
+/// Writes the current sessions to the `path`. The
+/// file is replaced at once, so a crash in the middle
+/// leaves the previous snapshot intact.
+pub async fn save_snapshot(context: &SusContext, path: &Path) -> Result<usize> {
+    let jail = context.jail.clone();
+    let clock = Clock::now()?;
+
+    let snapshot = context.game.call(move |state| take_snapshot(state, &jail, &clock)).await?;
+    let bytes = to_bytes(&snapshot)?;
+
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    std::fs::write(&temporary, &bytes)?;
+    std::fs::rename(&temporary, path)?;
+
+    Ok(snapshot.sessions.len())
+}
+
+pub fn read_snapshot(path: &Path) -> Result<Snapshot> {
+    let bytes = std::fs::read(path)?;
+    let header: SnapshotHeader = from_bytes(&bytes)?;
+
+    if header.version != SNAPSHOT_VERSION {
+        return misconfiguration(&format!(
+            "Unsupported snapshot version {} in {:?}, expected {}",
+            header.version, path, SNAPSHOT_VERSION,
+        ))
+    }
+
+    from_bytes(&bytes)
+}
+
+fn locate(jail: &Arc<Jail>, location: &str) -> PathBuf {
+    match jail.resolve(jail.root(), location) {
+        Lookup::Found(it) if it.is_dir() => it,
+        _ => jail.root().clone(),
+    }
+}
+
+/// Brings back the sessions saved by the previous
+/// run. Returns the number of the living ones.
+pub async fn restore_snapshot(context: &SusContext, path: &Path) -> Result<usize> {
+    let snapshot = read_snapshot(path)?;
+    let clock = Clock::now()?;
+
+    let mut alive = vec![];
+    let mut expired = vec![];
+
+    for it in snapshot.sessions {
+        if it.is_expired {
+            expired.push((it.identity, clock.to_instant(it.last_activity)));
+            continue
+        }
+
+        context.events.open(&it.identity)?;
+
+        let death = match (it.killed_by, it.killed_at) {
+            (Some(killer), Some(killed_at)) => Some(Death {
+                killer,
+                killed_at,
+            }),
+            _ => None,
+        };
+
+        alive.push(UserData {
+            name: it.name,
+            location: locate(&context.jail, &it.location),
+            identity: it.identity,
+            tag: it.tag,
+            created_at: clock.to_instant(it.created_at),
+            last_activity: clock.to_instant(it.last_activity),
+            death,
+            last_kill: it.last_kill_ago.map(|that| clock.ago(that)),
+        });
+    }
+
+    let count = alive.len();
+    let game_over = snapshot.game_over;
+
+    context.game.call(move |state| {
+        state.game_over = game_over;
+
+        for it in alive {
+            state.sessions.insert(it);
+        }
+
+        for (identity, evicted_at) in expired {
+            state.expired.insert(identity, evicted_at);
+        }
+    }).await?;
+
+    Ok(count)
+}
+
+/// Restores the sessions if there's anything to
+/// restore. A missing or unreadable snapshot only
+/// means the game starts from scratch.
+pub async fn restore_or_start_over(context: &SusContext, options: &SnapshotOptions) {
+    if !options.restore || !options.path.exists() {
+        return
+    }
+
+    match restore_snapshot(context, &options.path).await {
+        Ok(count) => info!("Restored {} sessions from {:?}", count, options.path),
+        Err(error) => warn!("Starting over, can't restore the sessions > {}", error),
+    }
+}
+
+pub async fn save_snapshots_periodically(context: SusContext, path: PathBuf, shutdown: Shutdown) {
+    let mut interval = tokio::time::interval(SNAPSHOT_PERIOD);
+
+    let stopped = shutdown.wait();
+    tokio::pin!(stopped);
+
+    // The first tick is immediate,
+    // there's nothing to save yet
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = &mut stopped => return,
+        }
+
+        let result = save_snapshot(&context, &path).await;
+        with_error_report(|| result.map(|_| ()));
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::{HashMap};
+use std::time::{Duration, Instant};
+
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use common::{Result, with_error_report};
+
+use crate::events::{EventKind};
+use crate::game::{GameState};
+use crate::members::{Members, Role};
+use crate::server::{SusContext, UserData};
+use crate::shutdown::{Shutdown};
+
+pub const DEFAULT_PER_CREWMATE: u32 = 3;
+pub const DEFAULT_STAY: u64 = 10;
+
+/// How often the stays and the
+/// win conditions are checked.
+pub const TASK_TICK: Duration = Duration::from_secs(1);
+
+/// The width of the progress bar.
+const BAR_WIDTH: usize = 20;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskKind {
+    /// `cd` into the directory.
+    Visit,
+    /// `ls` inside the directory.
+    Ls,
+    /// Stay in the directory for
+    /// `seconds` in a row.
+    Stay,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TaskSpec {
+    pub kind: TaskKind,
+    /// As seen from inside the jail, like `/common`.
+    pub path: String,
+    #[serde(default = "default_stay")]
+    pub seconds: u64,
+}
+
+fn default_stay() -> u64 {
+    DEFAULT_STAY
+}
+
+impl TaskSpec {
+    pub fn describe(&self) -> String {
+        match self.kind {
+            TaskKind::Visit => format!("visit {}", self.path),
+            TaskKind::Ls => format!("ls in {}", self.path),
+            TaskKind::Stay => format!("stay in {} for {} seconds", self.path, self.seconds),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TaskRules {
+    /// Drawn from the `list` for each crewmate.
+    pub per_crewmate: u32,
+    /// Empty by default, since the directories
+    /// depend on the root. No tasks, no task win.
+    pub list: Vec<TaskSpec>,
+}
+
+impl Default for TaskRules {
+    fn default() -> Self {
+        TaskRules {
+            per_crewmate: DEFAULT_PER_CREWMATE,
+            list: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Task {
+    pub spec: TaskSpec,
+    pub done: bool,
+    /// When the session has entered the
+    /// directory of a `Stay` task.
+    pub since: Option<Instant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Team {
+    Crew,
+    Impostors,
+}
+
+impl Team {
+    /// Those who may kill are impostors, and the admins
+    /// run the game rather than play it. Only meant for
+    /// the living who have logged in, see `plays()`.
+    pub fn of(role: &Role) -> Option<Team> {
+        if role.is_admin {
+            return None
+        }
+
+        match role.allowed_commands.iter().any(|it| it == "kill") {
+            true => Some(Team::Impostors),
+            false => Some(Team::Crew),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Team::Crew => "crew",
+            Team::Impostors => "impostors",
+        }
+    }
+}
+
+/// Whether the `user` is on either team, guests
+/// and ghosts are not.
+pub fn plays(user: &UserData) -> bool {
+    !user.is_ghost() && user.name != "guest"
+}
+
+/// Draws the tasks for the `user`, unless they
+/// have some already. A stay in the directory
+/// the user is in starts counting at once.
+pub fn assign(state: &mut GameState, user: &UserData, location: &str, rules: &TaskRules, now: Instant) {
+    if state.tasks.contains_key(&user.identity) {
+        return
+    }
+
+    let mut pool = rules.list.clone();
+    let mut drawn = vec![];
+
+    while drawn.len() < rules.per_crewmate as usize && !pool.is_empty() {
+        let index = OsRng.next_u32() as usize % pool.len();
+        let spec = pool.swap_remove(index);
+
+        let since = match spec.kind {
+            TaskKind::Stay if spec.path == location => Some(now),
+            _ => None,
+        };
+
+        drawn.push(Task {
+            spec,
+            done: false,
+            since,
+        });
+    }
+
+    state.tasks.insert(user.identity.clone(), drawn);
+}
+
+/// Marks the visits at the `location` done and starts
+/// the stay there, the stays elsewhere start over.
+pub fn arrive(state: &mut GameState, identity: &str, location: &str, now: Instant) {
+    let tasks = match state.tasks.get_mut(identity) {
+        Some(it) => it,
+        None => return,
+    };
+
+    for it in tasks.iter_mut().filter(|it| !it.done) {
+        match it.spec.kind {
+            TaskKind::Visit if it.spec.path == location => it.done = true,
+            TaskKind::Stay if it.spec.path == location => it.since = it.since.or(Some(now)),
+            TaskKind::Stay => it.since = None,
+            _ => {}
+        }
+    }
+}
+
+/// Marks the listings of the `location` done.
+pub fn list(state: &mut GameState, identity: &str, location: &str) {
+    if let Some(tasks) = state.tasks.get_mut(identity) {
+        for it in tasks.iter_mut() {
+            if it.spec.kind == TaskKind::Ls && it.spec.path == location {
+                it.done = true;
+            }
+        }
+    }
+}
+
+/// Marks the stays that have lasted long enough done.
+pub fn finish_stays(state: &mut GameState, now: Instant) {
+    for it in state.tasks.values_mut().flatten() {
+        if let (TaskKind::Stay, false, Some(since)) = (it.spec.kind, it.done, it.since) {
+            if now >= since + Duration::from_secs(it.spec.seconds) {
+                it.done = true;
+            }
+        }
+    }
+}
+
+/// The done and the total tasks of everyone.
+pub fn progress(tasks: &HashMap<String, Vec<Task>>) -> (usize, usize) {
+    let all = tasks.values().flatten();
+    let done = all.clone().filter(|it| it.done).count();
+    (done, all.count())
+}
+
+/// Like `[#####-----] 50% (3/6)`.
+pub fn progress_bar(done: usize, total: usize) -> String {
+    let filled = match total {
+        0 => 0,
+        _ => done * BAR_WIDTH / total,
+    };
+
+    let percent = match total {
+        0 => 0,
+        _ => done * 100 / total,
+    };
+
+    format!(
+        "[{}{}] {}% ({}/{})",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        percent,
+        done,
+        total,
+    )
+}
+
+/// Who has won, given the living teams and the tasks.
+/// The impostors can't win before anyone has died,
+/// or the first of them to log in would.
+pub fn winner(state: &GameState, crewmates: usize, impostors: usize) -> Option<Team> {
+    let (done, total) = progress(&state.tasks);
+    let has_anyone_died = state.sessions.iter().any(|it| it.is_ghost());
+
+    if total > 0 && done == total {
+        Some(Team::Crew)
+    } else if has_anyone_died && impostors > 0 && impostors >= crewmates {
+        Some(Team::Impostors)
+    } else {
+        None
+    }
+}
+
+/// Sorts the living members into the teams,
+/// leaving the admins out.
+fn teams_of(members: &Members, command_names: &[String], living: Vec<UserData>) -> Result<Vec<(UserData, Team)>> {
+    let mut teams = vec![];
+
+    for it in living {
+        let role = members.role_of(&it, command_names)?;
+
+        if let Some(team) = Team::of(&role) {
+            teams.push((it, team));
+        }
+    }
+
+    Ok(teams)
+}
+
+async fn check(context: &SusContext) -> Result<()> {
+    let now = Instant::now();
+    let members = context.members.clone();
+    let command_names = context.command_names.clone();
+    let rules = context.tasks.clone();
+    let jail = context.jail.clone();
+
+    // All in one job, so that no kill, ejection or new
+    // round can slip in between the teams and the verdict
+    let winner = context.game.call(move |state| -> Result<Option<Team>> {
+        if state.game_over.is_some() {
+            return Ok(None)
+        }
+
+        finish_stays(state, now);
+
+        // Those who have left take their tasks along
+        let sessions = &state.sessions;
+        state.tasks.retain(|identity, _| sessions.contains(identity));
+
+        let living: Vec<UserData> = state.sessions.iter()
+            .filter(|it| plays(it))
+            .cloned()
+            .collect();
+
+        let teams = teams_of(&*members.read()?, &command_names, living)?;
+
+        // Catches the sessions restored from a snapshot
+        // and those whose role has changed since
+        for (user, team) in &teams {
+            if *team == Team::Crew {
+                assign(state, user, &jail.to_virtual(&user.location), &rules, now);
+            }
+        }
+
+        let impostors = teams.iter().filter(|(_, it)| *it == Team::Impostors).count();
+        let crewmates = teams.len() - impostors;
+
+        let won = winner(state, crewmates, impostors);
+        state.game_over = won;
+        Ok(won)
+    }).await??;
+
+    let message = match winner {
+        Some(Team::Crew) => "Game over, the crew has won, all the tasks are done",
+        Some(Team::Impostors) => "Game over, the impostors have won, they are as many as the crew",
+        None => return Ok(()),
+    };
+
+    log::info!("{}", message);
+    context.events.broadcast(EventKind::GameOver, message)
+}
+
+/// Counts the stays and ends the game
+/// once either team has won.
+pub async fn run_tasks(context: SusContext, shutdown: Shutdown) {
+    let mut interval = tokio::time::interval(TASK_TICK);
+
+    let stopped = shutdown.wait();
+    tokio::pin!(stopped);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = &mut stopped => return,
+        }
+
+        let result = check(&context).await;
+        with_error_report(|| result);
+    }
+}